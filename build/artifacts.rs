@@ -0,0 +1,226 @@
+use build_config;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// The headers each bundled library installs, and a function its archive
+// has to define for the build to be of any use.
+fn expected(package: &str) -> (&'static [&'static str], &'static str) {
+    match package {
+        "newt" => (&["newt.h"], "newtInit"),
+        "popt" => (&["popt.h"], "poptGetContext"),
+        "slang" => (&["slang.h", "slcurses.h"], "SLang_init_tty"),
+        _ => panic!("Unexpected package requested to be checked: {}", package)
+    }
+}
+
+// Whether the archive `data` mentions `symbol`. Names are NUL-terminated
+// in the archive's symbol table and in the string tables of its members,
+// and a Mach-O `_` prefix doesn't get in the way.
+fn mentions(data: &[u8], symbol: &str) -> bool {
+    let mut name = symbol.as_bytes().to_vec();
+    name.push(0);
+    data.windows(name.len()).any(|window| window == &name[..])
+}
+
+// cargo's names for architectures, with the ELF e_machine and Mach-O
+// cputype of their objects.
+const MACHINES: &[(&str, u16, Option<u32>)] = &[
+    ("x86_64", 62, Some(0x0100_0007)),
+    ("x86", 3, Some(7)),
+    ("aarch64", 183, Some(0x0100_000c)),
+    ("arm", 40, Some(12)),
+    ("riscv64", 243, None),
+    ("riscv32", 243, None),
+    ("powerpc64", 21, None),
+    ("powerpc", 20, None),
+    ("s390x", 22, None),
+    ("mips64", 8, None),
+    ("mips", 8, None),
+    ("loongarch64", 258, None),
+    ("sparc64", 43, None),
+    ("sparc", 2, None)
+];
+
+// The same for COFF objects, which have no magic number to tell them by
+// and are only looked at for Windows targets.
+const COFF_MACHINES: &[(&str, u16)] = &[
+    ("x86_64", 0x8664),
+    ("x86", 0x14c),
+    ("aarch64", 0xaa64),
+    ("arm", 0x1c4)
+];
+
+// The contents of the first member of the ar archive `data` that isn't
+// a symbol or string table, and its name.
+fn first_object(data: &[u8]) -> Option<(String, &[u8])> {
+    let mut pos = 8;
+    while pos + 60 <= data.len() {
+        let header = &data[pos..pos + 60];
+        let name = String::from_utf8_lossy(&header[..16]).trim().to_string();
+        let size: usize = String::from_utf8_lossy(&header[48..58]).trim()
+            .parse().ok()?;
+        let start = pos + 60;
+        let member = data.get(start..start + size)?;
+        pos = start + size + size % 2;
+
+        if name == "/" || name == "//" || name == "/SYM64/" ||
+           name.starts_with("__.SYMDEF") {
+            continue;
+        }
+        // BSD ar puts long names in front of the member's contents.
+        if let Some(len) = name.strip_prefix("#1/") {
+            let len: usize = len.parse().ok()?;
+            let long_name = member.get(..len)?;
+            let long_name = String::from_utf8_lossy(long_name)
+                .trim_end_matches('\0')
+                .to_string();
+            if long_name.starts_with("__.SYMDEF") {
+                continue;
+            }
+            return Some((long_name, member.get(len..)?));
+        }
+        return Some((name.trim_end_matches('/').to_string(), member));
+    }
+    None
+}
+
+// The architecture `object` was compiled for, if it is an object file this
+// knows about. LLVM bitcode from LTO builds isn't.
+fn object_arch(object: &[u8]) -> Option<String> {
+    let u16_at = |pos: usize, little: bool| {
+        let bytes = [*object.get(pos)?, *object.get(pos + 1)?];
+        Some(if little {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    };
+
+    if object.starts_with(b"\x7fELF") {
+        let machine = u16_at(18, *object.get(5)? == 1)?;
+        let arch = MACHINES.iter().find(|m| m.1 == machine)
+            .map(|m| m.0.to_string())
+            .unwrap_or_else(|| format!("ELF machine {}", machine));
+        return Some(arch);
+    }
+
+    if object.starts_with(&[0xce, 0xfa, 0xed, 0xfe]) ||
+       object.starts_with(&[0xcf, 0xfa, 0xed, 0xfe]) {
+        let cputype = u32::from_le_bytes([*object.get(4)?, *object.get(5)?,
+                                          *object.get(6)?, *object.get(7)?]);
+        let arch = MACHINES.iter().find(|m| m.2 == Some(cputype))
+            .map(|m| m.0.to_string())
+            .unwrap_or_else(|| format!("Mach-O CPU type {:#x}", cputype));
+        return Some(arch);
+    }
+
+    if build_config::rust_target().contains("-windows") {
+        let machine = u16_at(0, true)?;
+        return COFF_MACHINES.iter().find(|m| m.1 == machine)
+            .map(|m| m.0.to_string());
+    }
+    None
+}
+
+// Whether objects for `found` link into binaries for `target`. Only the
+// architectures in MACHINES are checked, and as far as their ELF machine
+// goes: it doesn't tell riscv32 from riscv64 or mips from mips64.
+fn same_arch(found: &str, target: &str) -> bool {
+    let elf_machine = |arch: &str| {
+        MACHINES.iter().find(|m| m.0 == arch).map(|m| m.1)
+    };
+    match elf_machine(target) {
+        Some(machine) => found == target || elf_machine(found) == Some(machine),
+        None => true
+    }
+}
+
+// Fail if the objects in `archive` were compiled for another architecture
+// than the cargo target, as when configure used the build machine's
+// compiler, rather than leave that to the final link.
+fn check_arch(archive: &Path, data: &[u8]) -> Result<(), String> {
+    let target_arch = match env::var("CARGO_CFG_TARGET_ARCH") {
+        Ok(arch) => arch,
+        Err(_) => return Ok(())
+    };
+    let (member, found) = match first_object(data) {
+        Some((member, object)) => match object_arch(object) {
+            Some(found) => (member, found),
+            None => return Ok(())
+        },
+        None => return Ok(())
+    };
+
+    if same_arch(&found, &target_arch) {
+        return Ok(());
+    }
+    Err(format!(
+        "{} in {} was compiled for {} rather than {}; the C compiler used \
+         doesn't build for the cargo target. Point CC_{} (or CC) at a \
+         compiler for it", member, archive.display(), found, target_arch,
+        build_config::rust_target()))
+}
+
+fn check_archive(package: &str, lib_dir: &Path) -> Result<(), String> {
+    let archive = lib_dir.join(format!("lib{}.a", package));
+    let data = fs::read(&archive).map_err(|e| {
+        format!("the build didn't install {}: {}", archive.display(), e)
+    })?;
+
+    let (_, symbol) = expected(package);
+    if !mentions(&data, symbol) {
+        return Err(format!(
+            "{} doesn't define {}; the build produced an empty or broken \
+             library", archive.display(), symbol));
+    }
+    check_arch(&archive, &data)
+}
+
+fn check_headers(package: &str, include_dir: &Path) -> Result<(), String> {
+    let (headers, _) = expected(package);
+    for header in headers.iter() {
+        let path = include_dir.join(header);
+        if !path.is_file() {
+            return Err(format!("the build didn't install {}",
+                               path.display()));
+        }
+    }
+    Ok(())
+}
+
+// The .pc file has to describe the installation in `prefix`, not some
+// other installation of the library.
+fn check_pkg_config(package: &str, pc_name: &str, prefix: &Path)
+    -> Result<(), String> {
+    let pc = prefix.join("lib/pkgconfig").join(format!("{}.pc", pc_name));
+    let contents = fs::read_to_string(&pc).map_err(|e| {
+        format!("the build didn't install {}: {}", pc.display(), e)
+    })?;
+
+    let expected = build_config::shell_path(&prefix.display().to_string());
+    let found = contents.lines()
+        .find_map(|line| line.strip_prefix("prefix="))
+        .map(str::trim);
+    match found {
+        Some(found) if build_config::shell_path(found) == expected => Ok(()),
+        Some(found) => Err(format!(
+            "{} points to {} rather than {}, where {} was installed",
+            pc.display(), found, expected, package)),
+        None => Err(format!("{} doesn't set a prefix", pc.display()))
+    }
+}
+
+// Check that the installation of `package` in `prefix` has its static
+// library, headers and, for `pc_name`, its .pc file, so that a broken
+// build fails here rather than in pkg-config or the final link.
+pub fn check(package: &str, prefix: &Path, pc_name: Option<&str>)
+    -> Result<(), String> {
+    check_archive(package, &prefix.join("lib"))?;
+    check_headers(package, &prefix.join("include"))?;
+    if let Some(pc_name) = pc_name {
+        check_pkg_config(package, pc_name, prefix)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use bindgen::callbacks::{IntKind,ParseCallbacks};
+use library::Library;
+
+use std::path::Path;
+
+// The pregenerated bindings give newt's integer macros a C int type.
+#[derive(Debug)]
+struct NewtMacros;
+
+impl ParseCallbacks for NewtMacros {
+    fn int_macro(&self, name: &str, _value: i64) -> Option<IntKind> {
+        if name.starts_with("NEWT_") {
+            Some(IntKind::Int)
+        } else {
+            None
+        }
+    }
+}
+
+// Generate `{out_dir}/bindings.rs` from the newt.h of the library being
+// linked.
+pub fn generate(lib: &Library, out_dir: &Path) -> Result<(), String> {
+    let mut include_paths = lib.include_paths.clone();
+    if include_paths.is_empty() {
+        if let Some(ref root) = lib.root {
+            include_paths.push(root.join("include"));
+        }
+    }
+
+    let mut builder = bindgen::Builder::default()
+        .header_contents("newt-sys.h", "#include <newt.h>\n")
+        // Character macros are declared in lib.rs.
+        .blocklist_item(
+            "NEWT_CHECKBOXTREE_(COLLAPSED|EXPANDED|UNSELECTED|SELECTED)")
+        .parse_callbacks(Box::new(NewtMacros))
+        .parse_callbacks(Box::new(
+            bindgen::CargoCallbacks::new().rerun_on_header_files(false)));
+    for path in include_paths.iter() {
+        builder = builder.clang_arg(format!("-I{}", path.display()));
+    }
+
+    let bindings = builder.generate().map_err(|e| e.to_string())?;
+    let path = out_dir.join("bindings.rs");
+    bindings.write_to_file(&path)
+        .map_err(|e| format!("unable to write {}: {}", path.display(), e))
+}
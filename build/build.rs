@@ -0,0 +1,2025 @@
+#[cfg(feature = "buildtime-bindgen")]
+extern crate bindgen;
+extern crate bzip2;
+extern crate flate2;
+extern crate lazy_static;
+extern crate pkg_config;
+extern crate regex;
+extern crate sha2;
+extern crate shlex;
+extern crate tar;
+#[cfg(feature = "vcpkg")]
+extern crate vcpkg;
+
+mod artifacts;
+#[cfg(feature = "buildtime-bindgen")]
+mod bindings;
+mod build_config;
+mod build_info;
+mod cache;
+mod cc_build;
+mod checksum;
+mod deps_report;
+#[cfg(any(feature = "download-sources", feature = "bundle-cache"))]
+mod download;
+mod error;
+mod extract;
+mod gnuconfig;
+mod library;
+mod patches;
+#[cfg(feature = "bundle-cache")]
+mod prebuilt;
+mod preflight;
+mod relocate;
+mod reproducible;
+mod source_dir;
+mod stamp;
+mod symbols;
+mod timings;
+mod version;
+
+use build_config::env_var;
+use error::{BuildError,Phase};
+use lazy_static::lazy_static;
+use library::Library;
+use preflight::Prerequisite;
+use regex::Regex;
+use stamp::Stamp;
+use timings::Timings;
+
+use std::{env, fs, process};
+use std::cmp::Ordering;
+use std::io::{self,Write};
+use std::path::{Path,PathBuf};
+use std::process::{Command,ExitStatus,Output,Stdio};
+use std::time::Instant;
+
+const NEWT_VERSION:   &str = "0.52.24";
+const POPT_VERSION:   &str = "1.19";
+const SLANG_VERSION:  &str = "2.3.3";
+
+// Oldest system libnewt the pregenerated bindings support.
+const NEWT_MIN_VERSION: &str = "0.52.18";
+
+const POPT_SHA256:  &str =
+    "c25a4838fc8e4c1c8aacb8bd620edb3084a3d63bf8987fdad3ca2758c63240f9";
+const SLANG_SHA256: &str =
+    "f9145054ae131973c61208ea82486d5dd10e3c5cdad23b7c4a0617743c8f5a18";
+
+const STAMP_FILE: &str = ".newt-sys-stamp";
+const LOG_DIR: &str = "newt-sys-logs";
+
+// Compiled against a system libnewt's headers before it is used. It only
+// has to compile, it's never linked.
+const HEADER_CHECK: &str = "#include <stddef.h>\n\
+                            #include <newt.h>\n\n\
+                            int main(void) {\n    \
+                            newtComponent form = newtForm(NULL, NULL, 0);\n    \
+                            return form == NULL;\n\
+                            }\n";
+
+// newt archives that can be selected with NEWT_SYS_NEWT_VERSION, with their
+// checksums. NEWT_VERSION is the default.
+const NEWT_ARCHIVES: &[(&str, &str)] = &[
+    ("0.52.24",
+     "5ded7e221f85f642521c49b1826c8de19845aa372baf5d630a51774b544fbdbb")
+];
+
+// newt releases that get a `newt_0_52_N` cfg when the linked libnewt is at
+// least that version.
+const NEWT_RELEASES: &[&str] = &[
+    "0.52.18", "0.52.19", "0.52.20", "0.52.21", "0.52.22", "0.52.23",
+    "0.52.24"
+];
+
+// Pregenerated bindings in src/bindings, by the first newt version they
+// match. newt.h hasn't changed since 0.52.18.
+const BINDINGS: &[(&str, &str)] = &[
+    ("0.52.18", "newt_0_52_18.rs")
+];
+
+lazy_static! {
+    static ref MAKE: Option<&'static str> = find_gnu_make();
+    static ref FILE_PREFIX_MAP: Option<String> = file_prefix_map();
+}
+
+struct BuildConfig<'a> {
+    src_dir: &'a str,
+    install_prefix: &'a str,
+    cppflags: Option<&'a str>,
+    ldflags: Option<&'a str>
+}
+
+enum Origin<'a> {
+    // A release, by its version and the SHA-256 of its archive. The archive
+    // is only looked up, or downloaded, when it is unpacked.
+    Release(String, String),
+    Tree(&'a Path)
+}
+
+fn check_make(make: &str) -> bool {
+    let cmd = Command::new(make)
+        .stdin(Stdio::null())
+        .args(["-f", "-", "--version"])
+        .output();
+
+    match cmd {
+        Ok(output) => {
+            let re = Regex::new(r"\AGNU Make").unwrap();
+            let s = String::from_utf8_lossy(output.stdout.as_slice());
+            re.is_match(&s)
+        },
+        Err(_e) => false
+    }
+}
+
+fn find_gnu_make() -> Option<&'static str> {
+    // /usr/bin/make on macOS is an xcrun shim for GNU Make 3.81, prefer a
+    // gmake from Homebrew or MacPorts when there is one. On the BSDs `make`
+    // is never GNU Make.
+    let candidates: &[&'static str] = if cfg!(target_os = "macos") {
+        &["gmake", "make"]
+    } else if cfg!(any(target_os = "freebsd", target_os = "openbsd",
+                       target_os = "netbsd", target_os = "dragonfly")) {
+        &["gmake"]
+    } else {
+        &["make", "gmake"]
+    };
+
+    candidates.iter()
+        .find(|make| check_make(make))
+        .copied()
+}
+
+// Full transcript of the commands run to build `package`, kept since
+// errors only show the end of their output.
+fn log_path(package: &str) -> Option<PathBuf> {
+    let out_dir = env::var_os("OUT_DIR")?;
+    Some(Path::new(&out_dir).join(LOG_DIR).join(format!("{}.log", package)))
+}
+
+fn clear_log(package: &str) {
+    if let Some(path) = log_path(package) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+// Append `cmd` and what it printed to the log of `package`. Logging is
+// best effort; the path is only returned if the log could be written.
+fn append_log(package: &str, cmd: &Command, status: Option<ExitStatus>,
+              output: Option<&Output>) -> Option<PathBuf> {
+    let path = log_path(package)?;
+    fs::create_dir_all(path.parent()?).ok()?;
+    let mut log = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .ok()?;
+
+    writeln!(log, "$ {:?}", cmd).ok()?;
+    match output {
+        Some(output) => {
+            log.write_all(&output.stdout).ok()?;
+            log.write_all(&output.stderr).ok()?;
+        },
+        None => writeln!(log, "(not captured with NEWT_SYS_VERBOSE)").ok()?
+    }
+    match status {
+        Some(status) => writeln!(log, "## {}\n", status).ok()?,
+        None => writeln!(log, "## unable to execute\n").ok()?
+    }
+    Some(path)
+}
+
+fn run(cmd: &mut Command, package: &str, phase: Phase)
+    -> Result<(), BuildError> {
+    let spawn_error = |e: io::Error, cmd: &Command| {
+        BuildError::new(package, phase, format!("unable to execute: {}", e))
+            .command(format!("{:?}", cmd))
+            .log(append_log(package, cmd, None, None).as_deref())
+    };
+
+    if build_config::verbose() {
+        let status = cmd.stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .map_err(|e| spawn_error(e, cmd))?;
+
+        let log = append_log(package, cmd, Some(status), None);
+        if !status.success() {
+            return Err(BuildError::new(package, phase, "command failed")
+                .command(format!("{:?}", cmd))
+                .status(status)
+                .log(log.as_deref()));
+        }
+        return Ok(());
+    }
+
+    let output = cmd.output().map_err(|e| spawn_error(e, cmd))?;
+    let log = append_log(package, cmd, Some(output.status), Some(&output));
+    if !output.status.success() {
+        let mut stderr = io::stderr();
+        let _ = stderr.write_all(&output.stdout);
+        let _ = stderr.write_all(&output.stderr);
+        return Err(BuildError::new(package, phase, "command failed")
+            .command(format!("{:?}", cmd))
+            .status(output.status)
+            .stderr(&output.stderr)
+            .log(log.as_deref()));
+    }
+    Ok(())
+}
+
+fn configure(cfg: &BuildConfig) -> Command {
+    let script = format!("{}/configure", cfg.src_dir);
+
+    // Windows can't execute the script itself, run it with the MSYS2 or
+    // Cygwin shell.
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("sh");
+        cmd.arg(build_config::shell_path(&script));
+        cmd
+    } else {
+        Command::new(script)
+    };
+
+    confine_temp_files(&mut cmd).current_dir(cfg.src_dir)
+        .arg("--prefix")
+        .arg(build_config::shell_path(cfg.install_prefix))
+        .env("CFLAGS", if build_config::static_pie() {
+            cflags_with_fpic()
+        } else {
+            cflags()
+        })
+        .env("CC", build_config::cc_command())
+        .env("AR", build_config::archiver_env())
+        .env("RANLIB", build_config::ranlib_env());
+
+    if let Some(strip) = build_config::strip() {
+        cmd.env("STRIP", strip);
+    }
+
+    if build_config::is_cross() {
+        cmd.arg(format!("--build={}", build_config::autoconf_build()))
+            .arg(format!("--host={}", build_config::target()));
+    }
+
+    if let Some(cppflags) = merge_flags(cfg.cppflags, "CPPFLAGS") {
+        cmd.env("CPPFLAGS", cppflags);
+    }
+
+    if let Some(ldflags) = merge_flags(cfg.ldflags, "LDFLAGS") {
+        cmd.env("LDFLAGS", ldflags);
+    }
+    cmd
+}
+
+// The crate's flags followed by the user's `var`, as set by Nix, Homebrew
+// or a distribution's build wrappers. The include and library paths of the
+// bundled libraries come first so that installed copies don't shadow them.
+fn merge_flags(flags: Option<&str>, var: &str) -> Option<String> {
+    let user = build_config::target_var(var).filter(|val| !val.is_empty());
+    match (flags, user) {
+        (Some(flags), Some(user)) => Some(format!("{} {}", flags, user)),
+        (Some(flags), None) => Some(flags.to_string()),
+        (None, user) => user
+    }
+}
+
+fn can_autoreconf(src_dir: &Path) -> bool {
+    let has_input = ["configure.ac", "configure.in"].iter()
+        .any(|file| src_dir.join(file).is_file());
+    has_input && Command::new("autoreconf")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+// Regenerate the configure script, and with it the config.guess and
+// config.sub of packages using automake. Needed for trees without a
+// generated configure and for hosts the shipped scripts don't know.
+fn autoreconf(package: &str, src_dir: &Path) -> Result<(), BuildError> {
+    // autoreconf is a perl script, Windows can't execute it directly.
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "autoreconf -fi"]);
+        cmd
+    } else {
+        let mut cmd = Command::new("autoreconf");
+        cmd.arg("-fi");
+        cmd
+    };
+    run(confine_temp_files(&mut cmd).current_dir(src_dir), package,
+        Phase::Configure)
+}
+
+fn configure_newt(cfg: &BuildConfig) -> Command {
+    let mut cmd = configure(cfg);
+    cmd.arg("--without-python")
+        .arg("--without-tcl");
+
+    // The shared libnewt is compiled with the same CFLAGS, and can't be
+    // linked from -fPIE objects.
+    if build_config::hardening() {
+        cmd.env("CFLAGS", cflags_with_fpic());
+    }
+
+    // whiptail binds its messages to the catalogs installed along with it.
+    if cfg!(feature = "nls") {
+        let locale_dir = build_config::shell_path(cfg.install_prefix) +
+                         "/share/locale";
+        let mut cppflags: Vec<String> = cfg.cppflags.map(String::from)
+            .into_iter()
+            .chain(Some(format!("-DLOCALEDIR='\"{}\"'",
+                                locale_dir.replace('\'', "'\\''"))))
+            .collect();
+        let mut ldflags: Vec<String> = cfg.ldflags.map(String::from)
+            .into_iter()
+            .collect();
+        // libintl comes from the package collection on the BSDs.
+        if let Some(prefix) = build_config::local_prefix() {
+            cppflags.push(format!("-I{}/include", prefix));
+            ldflags.push(format!("-L{}/lib", prefix));
+        }
+
+        cmd.arg("--enable-nls");
+        if let Some(cppflags) = merge_flags(Some(&cppflags.join(" ")),
+                                            "CPPFLAGS") {
+            cmd.env("CPPFLAGS", cppflags);
+        }
+        if let Some(ldflags) = merge_flags(Some(&ldflags.join(" ")),
+                                           "LDFLAGS") {
+            cmd.env("LDFLAGS", ldflags);
+        }
+    } else {
+        cmd.arg("--disable-nls");
+    }
+
+    // newt talks to the GPM daemon itself, there's no libgpm to link.
+    if cfg!(feature = "gpm") {
+        cmd.arg("--with-gpm-support");
+    }
+    cmd
+}
+
+fn configure_popt(cfg: &BuildConfig) -> Command {
+    let mut cmd = configure(cfg);
+    cmd.arg("--disable-nls")
+        .arg("--disable-rpath");
+
+    if build_config::is_darwin() {
+        cmd.arg("--without-libiconv-prefix");
+    } else if let Some(prefix) = build_config::local_prefix() {
+        cmd.arg(format!("--with-libiconv-prefix={}", prefix));
+    }
+
+    if build_config::is_musl() {
+        cmd.arg("--disable-shared");
+    }
+    cmd
+}
+
+fn configure_slang(cfg: &BuildConfig) -> Command {
+    let mut cmd = configure(cfg);
+    cmd.env("CFLAGS", cflags_with_fpic());
+
+    // slang only switches to UTF-8 for UTF-8 locales if it can query the
+    // locale's codeset. Don't let a failed configure check disable that.
+    if cfg!(feature = "utf8") {
+        cmd.env("ac_cv_header_locale_h", "yes")
+            .env("ac_cv_header_langinfo_h", "yes")
+            .env("jd_cv_nl_langinfo_codeset", "yes");
+    }
+
+    // Keep slang from picking up optional modules from the host, newt needs
+    // none of them.
+    if cfg!(feature = "slang-minimal") {
+        cmd.args(["--without-pcre", "--without-onig", "--without-png",
+                  "--without-z", "--without-iconv", "--without-x"]);
+    }
+    cmd
+}
+
+fn make_target(package: &str) -> &'static str {
+    match package {
+        // `install` also builds whiptail, which needs popt.
+        "newt" if cfg!(feature = "no-popt") => "install-sh",
+        "slang" => "install-static",
+        _ => "install"
+    }
+}
+
+fn make_args(package: &str, install_prefix: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    // install-sh only installs the shared libnewt, libnewt.a is installed
+    // by install_files.
+    if package == "newt" && cfg!(feature = "no-popt") {
+        args.push(String::from("libnewt.a"));
+    }
+    // The top-level install-static also builds and installs slsh. Only
+    // libslang.a and its headers are installed from src, without the
+    // documentation, and slang.pc by install_files.
+    if package == "slang" {
+        args.extend([String::from("-C"), String::from("src")]);
+    }
+    args.push(String::from(make_target(package)));
+    if package == "slang" {
+        args.push(String::from("DOC_FILES="));
+    }
+
+    // newt's Makefile hard-codes GNU ld flags for the shared library, which
+    // the macOS linker rejects.
+    if package == "newt" && build_config::is_darwin() {
+        let install_name = if cfg!(feature = "shared") {
+            String::from("@rpath/libnewt.dylib")
+        } else {
+            format!("{}/lib/libnewt.dylib", install_prefix)
+        };
+        args.push(String::from("SOEXT=dylib"));
+        args.push(format!("SHLIBFLAGS=-Wl,-install_name,{}", install_name));
+    }
+
+    // slang's Makefile quotes MISC_TERMINFO_DIRS as a single directory.
+    if package == "slang" {
+        if let Some(dirs) = build_config::misc_terminfo_dirs() {
+            args.push(format!("sltermin_C_FLAGS=-DMISC_TERMINFO_DIRS='{}'",
+                              dirs.replace('$', "$$")
+                                  .replace('\'', "'\\''")));
+        }
+    }
+    args
+}
+
+#[inline]
+fn make(package: &str) -> Result<&'static str, BuildError> {
+    MAKE.ok_or_else(|| {
+        BuildError::new(package, Phase::Prepare,
+                        "GNU Make is required for building this package")
+    })
+}
+
+// Write a wrapper named `name` running `program` into `bin_dir`, with PATH
+// set to `path` if given.
+fn shim(bin_dir: &Path, name: &str, program: &str, path: Option<&str>)
+    -> io::Result<()> {
+    fs::create_dir_all(bin_dir)?;
+
+    let mut script = String::from("#!/bin/sh\n");
+    if let Some(path) = path {
+        script.push_str(&format!("PATH='{}'\nexport PATH\n",
+                                 path.replace('\'', "'\\''")));
+    }
+    script.push_str(&format!("exec {} \"$@\"\n", program));
+
+    let shim = bin_dir.join(name);
+    fs::write(&shim, script)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&shim, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+fn make_command(package: &str, out_dir: &Path)
+    -> Result<Command, BuildError> {
+    let make = make(package)?;
+    let mut cmd = Command::new(make);
+    confine_temp_files(&mut cmd);
+
+    // newt's install target runs a plain `make` in po/, which has to be GNU
+    // Make as well, and the newt and slang Makefiles run a plain `ar`. The
+    // archiver runs with the original PATH, it may be a script calling `ar`.
+    let archiver = build_config::archiver_env();
+    let archiver = archiver.to_string_lossy();
+    let path = env::var("PATH").ok();
+    let mut shims = Vec::new();
+    if make != "make" {
+        shims.push(("make", make, None));
+    }
+    if archiver != "ar" {
+        shims.push(("ar", &archiver, path.as_deref()));
+    }
+
+    let bin_dir = Path::new(out_dir).join("bin");
+    let _ = fs::remove_dir_all(&bin_dir);
+    if !shims.is_empty() {
+        let shim_error = |e: String| {
+            BuildError::new(package, Phase::Prepare,
+                            format!("unable to set up a tool wrapper: {}", e))
+        };
+        for &(name, program, path) in shims.iter() {
+            shim(&bin_dir, name, program, path)
+                .map_err(|e| shim_error(e.to_string()))?;
+        }
+        let mut paths = vec![bin_dir];
+        if let Some(path) = env::var_os("PATH") {
+            paths.extend(env::split_paths(&path));
+        }
+        let path = env::join_paths(paths)
+            .map_err(|e| shim_error(e.to_string()))?;
+        cmd.env("PATH", path);
+    }
+
+    if build_config::is_darwin() {
+        cmd.env("ZERO_AR_DATE", "1");
+    }
+
+    // Share cargo's job tokens through its jobserver rather than running
+    // NUM_JOBS jobs on top of everything cargo is already running.
+    if let Ok(makeflags) = env::var("CARGO_MAKEFLAGS") {
+        cmd.env("MAKEFLAGS", makeflags);
+    } else if let Ok(jobs) = env::var("NUM_JOBS") {
+        cmd.arg(format!("-j{}", jobs));
+    }
+    Ok(cmd)
+}
+
+// Remove the debug info from the static libraries in `lib_dir`.
+fn strip_archives(package: &str, lib_dir: &Path) -> Result<(), BuildError> {
+    let (program, option) = build_config::debug_stripper();
+    let entries = fs::read_dir(lib_dir).map_err(|e| {
+        BuildError::new(package, Phase::Make,
+                        format!("unable to read {}: {}", lib_dir.display(), e))
+    })?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "a") {
+            run(Command::new(&program).arg(option).arg(&path), package,
+                Phase::Make)?;
+        }
+    }
+    Ok(())
+}
+
+// The file name of a program for the cargo target.
+fn exe_name(name: &str) -> String {
+    if build_config::rust_target().contains("-windows") {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    }
+}
+
+// Install what the make targets in make_args leave out of `install_prefix`:
+// libnewt.a without popt, and slang.pc.
+fn install_files(package: &str, src_dir: &str, install_prefix: &str)
+    -> Result<(), BuildError> {
+    let files: &[(&str, &str)] = match package {
+        "newt" if cfg!(feature = "no-popt") => &[("libnewt.a", "lib")],
+        "slang" => &[("slang.pc", "lib/pkgconfig")],
+        _ => &[]
+    };
+
+    for &(name, dir) in files {
+        let dir = Path::new(install_prefix).join(dir);
+        let dest = dir.join(name);
+        fs::create_dir_all(&dir)
+            .and_then(|_| fs::copy(Path::new(src_dir).join(name), &dest))
+            .map_err(|e| {
+                BuildError::new(package, Phase::Make, format!(
+                    "unable to install {}: {}", dest.display(), e))
+            })?;
+    }
+
+    // What's left of slang's documentation without DOC_FILES.
+    if package == "slang" {
+        let _ = fs::remove_dir_all(Path::new(install_prefix).join("share"));
+    }
+    Ok(())
+}
+
+// The whiptail newt's Makefile installs uses libnewt and libpopt from
+// OUT_DIR without an rpath to find them there. Link it again against the
+// static libraries so that it runs from where it is installed.
+fn link_whiptail(src_dir: &str, install_prefix: &str, libs: &[Library])
+    -> Result<(), BuildError> {
+    let prefix = Path::new(install_prefix);
+    let mut cmd = build_config::compiler().to_command();
+    cmd.current_dir(src_dir)
+        .arg("-o")
+        .arg(prefix.join("bin").join(exe_name("whiptail")))
+        .args(["whiptail.o", "dialogboxes.o"])
+        .arg(prefix.join("lib/libnewt.a"));
+
+    for lib in libs.iter() {
+        for path in lib.link_paths.iter() {
+            cmd.arg(format!("-L{}", path.display()));
+        }
+        for name in lib.libs.iter() {
+            let archive = lib.link_paths.iter()
+                .map(|path| path.join(format!("lib{}.a", name)))
+                .find(|path| path.is_file());
+            match archive {
+                Some(archive) => cmd.arg(archive),
+                None => cmd.arg(format!("-l{}", name))
+            };
+        }
+    }
+    if cfg!(feature = "nls") && build_config::needs_libintl() {
+        if let Some(prefix) = build_config::local_prefix() {
+            cmd.arg(format!("-L{}/lib", prefix));
+        }
+        cmd.arg("-lintl");
+    }
+    cmd.args(build_config::sanitize_flag())
+        .args(build_config::lto_linker_flag());
+    run(&mut cmd, "newt", Phase::Make)
+}
+
+fn archive_ext(package: &str) -> &'static str {
+    match package {
+        "slang" => "tar.bz2",
+        _ => "tar.gz"
+    }
+}
+
+// Other newt releases can be built from an archive in vendor/ or from a
+// download, given their checksum in NEWT_SYS_NEWT_SHA256.
+fn archive_sha256(package: &str, version: &str) -> Result<String, BuildError> {
+    let sha256 = match package {
+        "newt" => NEWT_ARCHIVES.iter()
+            .find(|&&(release, _)| release == version)
+            .map(|&(_, sha256)| sha256.to_string())
+            .or_else(|| env_var("NEWT_SYS_NEWT_SHA256")),
+        "popt" => Some(POPT_SHA256.to_string()),
+        "slang" => Some(SLANG_SHA256.to_string()),
+        _ => panic!("Unexpected package requested to be built: {}", package)
+    };
+    sha256.ok_or_else(|| {
+        BuildError::new(package, Phase::Verify, format!(
+            "the checksum of {}-{} isn't known, set NEWT_SYS_NEWT_SHA256 \
+             to the SHA-256 of its archive", package, version))
+    })
+}
+
+// The bundled newt release to build, NEWT_SYS_NEWT_VERSION if set.
+fn newt_version() -> Result<String, BuildError> {
+    let version = match env_var("NEWT_SYS_NEWT_VERSION") {
+        Some(ref version) if !version.is_empty() => version.clone(),
+        _ => return Ok(NEWT_VERSION.to_string())
+    };
+
+    let valid = version.split('.').all(|part| {
+        !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())
+    });
+    if !valid {
+        return Err(BuildError::new("newt", Phase::Prepare,
+            format!("NEWT_SYS_NEWT_VERSION {} isn't a version number",
+                    version)));
+    }
+
+    if version::compare(&version, NEWT_MIN_VERSION) == Ordering::Less {
+        return Err(BuildError::new("newt", Phase::Prepare, format!(
+            "NEWT_SYS_NEWT_VERSION {} is older than {}, the oldest newt the \
+             bindings support", version, NEWT_MIN_VERSION)));
+    }
+    Ok(version)
+}
+
+#[cfg(feature = "download-sources")]
+fn archive_path(package: &str, version: &str, out_dir: &Path)
+    -> Result<PathBuf, BuildError> {
+    let archive = format!("{}-{}.{}", package, version, archive_ext(package));
+    download::download(package, &archive, &archive_sha256(package, version)?,
+                       &out_dir.join("download"))
+}
+
+#[cfg(not(feature = "download-sources"))]
+fn archive_path(package: &str, version: &str, _out_dir: &Path)
+    -> Result<PathBuf, BuildError> {
+    use std::io::Read;
+
+    let archive = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap())
+        .join("vendor")
+        .join(format!("{}-{}.{}", package, version, archive_ext(package)));
+    let bundled = match package {
+        "newt" => NEWT_ARCHIVES.iter().any(|&(release, _)| release == version),
+        _ => true
+    };
+    if !bundled && !archive.is_file() {
+        return Err(BuildError::new(package, Phase::Prepare, format!(
+            "{} {} isn't bundled, enable the `download-sources` feature to \
+             download it", package, version)));
+    }
+
+    // Checkouts without the archives, or with Git LFS pointers in their
+    // place, and repackaged sources that left vendor/ out.
+    let mut head = [0; 64];
+    let read = fs::File::open(&archive)
+        .and_then(|mut file| file.read(&mut head));
+    let problem = match read {
+        Err(_) => "is missing. Restore it from the newt-sys repository or \
+                   crate package",
+        Ok(n) if head[..n].starts_with(b"version https://git-lfs") =>
+            "is a Git LFS pointer. Run `git lfs pull` to fetch the archive",
+        Ok(_) => return Ok(archive)
+    };
+    Err(BuildError::new(package, Phase::Prepare, format!(
+        "{} {}, or enable the `download-sources` feature to download it \
+         instead", archive.display(), problem)))
+}
+
+fn cppflags(libs: &[Library]) -> Option<String> {
+    let flags: Vec<String> = libs.iter()
+        .flat_map(|lib| lib.include_paths.iter())
+        .map(|path| format!("-I{}", path.display()))
+        .collect();
+
+    if flags.is_empty() { None } else { Some(flags.join(" ")) }
+}
+
+// The programs the bundled packages build, such as whiptail, have to link the
+// sanitizer runtime their objects were instrumented for.
+fn ldflags(libs: &[Library]) -> Option<String> {
+    let flags: Vec<String> = libs.iter()
+        .flat_map(|lib| lib.link_paths.iter())
+        .map(|path| format!("-L{}", path.display()))
+        .chain(build_config::sanitize_flag())
+        .chain(build_config::lto_linker_flag())
+        .collect();
+
+    if flags.is_empty() { None } else { Some(flags.join(" ")) }
+}
+
+// Keeps OUT_DIR out of the debug info and __FILE__ strings of the bundled
+// libraries, so their builds are reproducible. Needs GCC 8 or clang 10.
+fn file_prefix_map() -> Option<String> {
+    let flag = format!("-ffile-prefix-map={}=.", env::var("OUT_DIR").ok()?);
+    match cc::Build::new().is_flag_supported(&flag) {
+        Ok(true) => Some(flag),
+        _ => None
+    }
+}
+
+// Optimization and debug info flags for the cargo profile, followed by the
+// user's CFLAGS so those can override them.
+fn cflags() -> String {
+    let mut flags: Vec<String> = Vec::new();
+
+    match env::var("OPT_LEVEL").as_deref() {
+        Ok("z") if build_config::compiler().is_like_clang() =>
+            flags.push(String::from("-Oz")),
+        Ok("s") | Ok("z") => flags.push(String::from("-Os")),
+        Ok(level) => flags.push(format!("-O{}", level)),
+        Err(_) => ()
+    }
+
+    match env::var("DEBUG").as_deref() {
+        Ok("false") | Ok("0") | Ok("none") | Err(_) => (),
+        Ok("line-tables-only") | Ok("1") | Ok("limited") =>
+            flags.push(String::from("-g1")),
+        Ok(_) => flags.push(String::from("-g"))
+    }
+
+    if let Some(ref flag) = *FILE_PREFIX_MAP {
+        flags.push(flag.clone());
+    }
+
+    if let Some(flag) = build_config::sanitize_flag() {
+        flags.push(flag);
+    }
+    flags.extend(build_config::lto_flags());
+
+    // cflags_with_fpic's -fPIC comes after -fPIE and takes precedence.
+    if build_config::hardening() {
+        flags.push(String::from("-fPIE"));
+    }
+    flags.extend(build_config::hardening_flags());
+
+    if let Some(cflags) = build_config::target_var("CFLAGS") {
+        flags.push(cflags);
+    }
+    flags.join(" ")
+}
+
+fn cflags_with_fpic() -> String {
+    let mut cflags = cflags();
+
+    if !cflags.contains("-fPIC") {
+        cflags.push_str(" -fPIC");
+    }
+    cflags
+}
+
+fn pkg_config_name(package: &str) -> &str {
+    match package {
+        "newt" => "libnewt",
+        _ => package
+    }
+}
+
+fn verify_configure(package: &str, src_dir: &str) -> Result<(), BuildError> {
+    if !cfg!(feature = "utf8") || package != "slang" {
+        return Ok(());
+    }
+
+    let sysconf_h = Path::new(src_dir).join("src/sysconf.h");
+    let sysconf = fs::read_to_string(&sysconf_h).map_err(|e| {
+        BuildError::new(package, Phase::Configure,
+                        format!("unable to read {}: {}",
+                                sysconf_h.display(), e))
+    })?;
+
+    if !sysconf.contains("#define HAVE_NL_LANGINFO_CODESET 1") {
+        return Err(BuildError::new(package, Phase::Configure,
+            "slang was configured without nl_langinfo(CODESET) and won't \
+             detect UTF-8 locales"));
+    }
+    Ok(())
+}
+
+// Where the sources of `package` come from, and how the build stamp
+// identifies them.
+fn origin<'a>(package: &str, version: &str, source: Option<&'a Path>)
+    -> Result<(Origin<'a>, String), BuildError> {
+    match source {
+        Some(dir) => {
+            println!("cargo:rerun-if-changed={}", dir.display());
+            let fingerprint = source_dir::fingerprint(dir)
+                .map_err(|e| BuildError::new(package, Phase::Prepare, e))?;
+            Ok((Origin::Tree(dir), fingerprint))
+        },
+        None => {
+            let sha256 = archive_sha256(package, version)?;
+            Ok((Origin::Release(version.to_string(), sha256.clone()), sha256))
+        }
+    }
+}
+
+fn configure_args(package: &str) -> Result<Vec<String>, BuildError> {
+    build_config::configure_args(package)
+        .map_err(|e| BuildError::new(package, Phase::Prepare, e))
+}
+
+fn find_patches(package: &str) -> Result<(Vec<PathBuf>, String), BuildError> {
+    let patches = patches::find(package)
+        .map_err(|e| BuildError::new(package, Phase::Patch, e))?;
+    let fingerprint = patches::fingerprint(&patches)
+        .map_err(|e| BuildError::new(package, Phase::Patch, e))?;
+    Ok((patches, fingerprint))
+}
+
+// Extract or copy the sources into `src_dir` and apply `patches` to them.
+fn unpack(package: &str, origin: &Origin, patches: &[PathBuf],
+          out_dir: &Path, src_dir: &Path) -> Result<(), BuildError> {
+    let build_prefix = &out_dir.join("build");
+    fs::create_dir_all(build_prefix).map_err(|e| {
+        BuildError::new(package, Phase::Extract,
+                        format!("unable to create {}: {}",
+                                build_prefix.display(), e))
+    })?;
+    match *origin {
+        Origin::Release(ref version, ref sha256) => {
+            let archive = archive_path(package, version, out_dir)?;
+            println!("cargo:rerun-if-changed={}", archive.display());
+            checksum::verify(&archive, sha256)
+                .map_err(|e| BuildError::new(package, Phase::Verify, e))?;
+            extract::extract(&archive, build_prefix)
+        },
+        Origin::Tree(dir) =>
+            source_dir::copy(dir, src_dir)
+    }.map_err(|e| BuildError::new(package, Phase::Extract, e))?;
+
+    for patch in patches.iter() {
+        run(Command::new("patch")
+                .current_dir(src_dir)
+                .arg("-p1")
+                .arg("-i")
+                .arg(patch),
+            package, Phase::Patch)?;
+    }
+    Ok(())
+}
+
+// Builds `package` from its archive, or from the tree at `source` if given.
+// configure and make only run in an OUT_DIR they can handle, see
+// make_can_handle(), so the paths given to them are strings here.
+fn build(package: &str, version: &str, source: Option<&Path>,
+         out_dir: &Path, libs: &[Library],
+         pkg_config_paths: &mut Vec<PathBuf>)
+    -> Result<Library, BuildError> {
+    let version_name = &format!("{}-{}", package, version);
+    let out_dir_str = &out_dir.display().to_string();
+    let src_dir = &format!("{}/build/{}", out_dir_str, version_name);
+
+    let (origin, source_line) = origin(package, version, source)?;
+    let (patches, patches_line) = find_patches(package)?;
+
+    let cppflags = cppflags(libs);
+    let ldflags = ldflags(libs);
+    let extra_args = configure_args(package)?;
+    let configure_for = |install_prefix: &str| {
+        let build_cfg = BuildConfig {
+            src_dir,
+            install_prefix,
+            cppflags: cppflags.as_deref(),
+            ldflags: ldflags.as_deref()
+        };
+        let mut cmd = match package {
+            "newt" => configure_newt(&build_cfg),
+            "popt" => configure_popt(&build_cfg),
+            "slang" => configure_slang(&build_cfg),
+            _ => panic!("Unexpected package requested to be built: {}",
+                        package)
+        };
+        cmd.args(&extra_args);
+        cmd
+    };
+
+    let gnuconfig_dir = gnuconfig::source_dir();
+    let gnuconfig_line = gnuconfig_dir.as_ref()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    let target = build_config::rust_target();
+    let source_date_epoch = env_var("SOURCE_DATE_EPOCH").unwrap_or_default();
+    let strip_debug = build_config::strip_debug();
+    let strip_line = if strip_debug { "debug" } else { "" };
+    let whiptail = package == "newt" && cfg!(feature = "whiptail");
+    let whiptail_line = if whiptail { "static" } else { "" };
+
+    // A cached build is installed under a name derived from everything
+    // that went into it except the installation prefix itself.
+    let cache = cache::dir().map(|dir| {
+        let configure = format!("{:?}", configure_for(cache::PREFIX));
+        let key = cache::key(&[
+            package, version, &source_line, &patches_line, &target,
+            &configure.replace(out_dir_str, cache::OUT_DIR),
+            &make_args(package, cache::PREFIX).join(" "), &gnuconfig_line,
+            &source_date_epoch, strip_line, whiptail_line
+        ]);
+        (dir, format!("{}-{}", version_name, key))
+    });
+    let _lock = match cache {
+        Some((ref dir, ref name)) => Some(cache::lock(dir, name)
+            .map_err(|e| BuildError::new(package, Phase::Prepare, e))?),
+        None => None
+    };
+    let install_prefix = &match cache {
+        Some((ref dir, ref name)) => dir.join(name).display().to_string(),
+        None => format!("{}/install/{}", out_dir_str, version_name)
+    };
+    // The build stamp stays valid when OUT_DIR or the cache is moved, as
+    // the installation is relocatable.
+    let stamp_value = |value: &str| {
+        value.replace(install_prefix.as_str(), cache::PREFIX)
+            .replace(out_dir_str, cache::OUT_DIR)
+    };
+
+    let mut configure = configure_for(install_prefix);
+    deps_report::built(package, "configure", Some(&configure));
+    let make_args = make_args(package, install_prefix);
+    let configure_line = format!("{:?}", configure);
+    let stamp = Stamp::new(&Path::new(install_prefix).join(STAMP_FILE), &[
+        ("package", package),
+        ("version", version),
+        ("source", &source_line),
+        ("patches", &patches_line),
+        ("target", &target),
+        ("cc", &build_config::cc_command().to_string_lossy()),
+        ("cflags", &stamp_value(&cflags())),
+        ("configure", &stamp_value(&configure_line)),
+        ("make", &stamp_value(&make_args.join(" "))),
+        ("gnuconfig", &gnuconfig_line),
+        ("source_date_epoch", &source_date_epoch),
+        ("strip", strip_line),
+        ("whiptail", whiptail_line)
+    ]);
+    // config.status holds absolute paths, so the source tree is only
+    // reused where it was configured.
+    let configured = Stamp::new(&Path::new(src_dir).join(STAMP_FILE), &[
+        ("package", package),
+        ("version", version),
+        ("source", &source_line),
+        ("patches", &patches_line),
+        ("target", &target),
+        ("cc", &build_config::cc_command().to_string_lossy()),
+        ("cflags", &cflags()),
+        ("configure", &configure_line),
+        ("gnuconfig", &gnuconfig_line),
+        ("source_date_epoch", &source_date_epoch)
+    ]);
+
+    let mut timings = Timings::new(package, version);
+    if !stamp.is_current() {
+        stamp.remove();
+        clear_log(package);
+        let _ = fs::remove_dir_all(install_prefix);
+        // A tree left behind by a failed build, or kept with
+        // NEWT_SYS_KEEP_BUILD, goes straight to make if it was configured
+        // the same way. Otherwise there is no tree, it is removed once
+        // installed.
+        let src_path = Path::new(src_dir);
+        if !configured.is_current_with("config.status") {
+            configured.remove();
+            let start = Instant::now();
+            unpack(package, &origin, &patches, out_dir, src_path)?;
+
+            if let Some(ref dir) = gnuconfig_dir {
+                gnuconfig::refresh(src_path, dir).map_err(|e| {
+                    BuildError::new(package, Phase::Extract, e)
+                })?;
+            }
+            timings.record("extract", start);
+
+            let start = Instant::now();
+
+            if !src_path.join("configure").is_file() {
+                if !can_autoreconf(src_path) {
+                    return Err(BuildError::new(package, Phase::Configure,
+                        "there is no configure script and autoreconf isn't \
+                         available to generate one"));
+                }
+                autoreconf(package, src_path)?;
+            }
+
+            match run(&mut configure, package, Phase::Configure) {
+                Ok(()) => (),
+                Err(ref e) if e.is_unknown_host() &&
+                              can_autoreconf(src_path) => {
+                    autoreconf(package, src_path)?;
+                    run(&mut configure, package, Phase::Configure)?;
+                },
+                Err(e) => return Err(e)
+            }
+            verify_configure(package, src_dir)?;
+            configured.write().map_err(|e| {
+                BuildError::new(package, Phase::Configure, format!(
+                    "unable to write configure stamp: {}", e))
+            })?;
+            timings.record("configure", start);
+        }
+        // make also runs the package's own install step.
+        let start = Instant::now();
+        run(make_command(package, out_dir)?
+                .current_dir(src_dir)
+                .args(&make_args),
+            package, Phase::Make)?;
+        timings.record("make", start);
+
+        let start = Instant::now();
+        install_files(package, src_dir, install_prefix)?;
+        if whiptail {
+            link_whiptail(src_dir, install_prefix, libs)?;
+        }
+
+        let lib_dir = Path::new(install_prefix).join("lib");
+        if strip_debug {
+            strip_archives(package, &lib_dir)?;
+        }
+
+        // Apple's ar and ranlib take care of this with ZERO_AR_DATE.
+        if !build_config::is_darwin() {
+            reproducible::normalize_archives(&lib_dir)
+                .map_err(|e| BuildError::new(package, Phase::Make, e))?;
+        }
+
+        artifacts::check(package, Path::new(install_prefix),
+                         Some(pkg_config_name(package)))
+            .map_err(|e| BuildError::new(package, Phase::Make, e))?;
+        relocate::pkg_config_files(Path::new(install_prefix))
+            .map_err(|e| BuildError::new(package, Phase::Make, e))?;
+
+        stamp.write().map_err(|e| {
+            BuildError::new(package, Phase::Make,
+                            format!("unable to write build stamp: {}", e))
+        })?;
+        timings.record("install", start);
+    }
+    timings.report();
+
+    // Only the install prefix is needed from here on.
+    if !build_config::keep_build() {
+        let _ = fs::remove_dir_all(src_dir);
+    }
+
+    probe_install(package, version, Path::new(install_prefix),
+                  pkg_config_paths)
+}
+
+// The library installed in `install_prefix`.
+fn probe_install(package: &str, version: &str, install_prefix: &Path,
+                 pkg_config_paths: &mut Vec<PathBuf>)
+    -> Result<Library, BuildError> {
+    // Ahead of system directories added for the `system-deps` feature,
+    // which may have a libnewt.pc of their own.
+    pkg_config_paths.insert(0, install_prefix.join("lib/pkgconfig"));
+    let search_paths: Vec<&Path> = pkg_config_paths.iter()
+        .map(PathBuf::as_path)
+        .collect();
+    let mut library = library::probe_installed(pkg_config_name(package),
+                                               version, &search_paths)
+        .map_err(|e| BuildError::new(package, Phase::Probe, e))?;
+    // Rather than the prefix relative to the .pc file.
+    library.root = Some(install_prefix.to_path_buf());
+
+    // popt uses iconv, which lives in its own library on some systems and
+    // may be given as a bare path in popt.pc.
+    if package == "popt" && build_config::needs_libiconv() &&
+       !library.libs.iter().any(|lib| lib == "iconv") {
+        if let Some(prefix) = build_config::local_prefix() {
+            library.link_paths.push(Path::new(prefix).join("lib"));
+        }
+        library.libs.push(String::from("iconv"));
+    }
+
+    // libnewt.pc doesn't list the libintl NLS makes newt use.
+    if package == "newt" && cfg!(feature = "nls") &&
+       build_config::needs_libintl() {
+        if let Some(prefix) = build_config::local_prefix() {
+            library.link_paths.push(Path::new(prefix).join("lib"));
+        }
+        library.libs.push(String::from("intl"));
+    }
+    Ok(library)
+}
+
+// Autoconf refuses to run in a directory with whitespace or shell
+// metacharacters in its name, and make splits paths at spaces. Both are
+// given the paths as strings, so they have to be valid UTF-8 as well.
+fn make_can_handle(path: &Path) -> bool {
+    match path.to_str() {
+        Some(path) => !path.chars()
+            .any(|c| c.is_whitespace() || "\"#$&'\\`".contains(c)),
+        None => false
+    }
+}
+
+// Why the bundled libraries are compiled with the cc crate rather than
+// their configure scripts and Makefiles, if they are.
+fn cc_build_reason() -> Option<&'static str> {
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap_or_default());
+    let cache_dir = cache::dir().unwrap_or_default();
+
+    if cfg!(feature = "cc-build") {
+        Some("with the `cc-build` feature")
+    } else if MAKE.is_none() {
+        Some("without GNU Make")
+    } else if !make_can_handle(&out_dir) || !make_can_handle(&cache_dir) {
+        Some("when OUT_DIR or NEWT_SYS_CACHE_DIR has spaces, shell \
+              metacharacters or invalid UTF-8 in it")
+    } else {
+        None
+    }
+}
+
+fn use_cc_build() -> bool {
+    cc_build_reason().is_some()
+}
+
+// What the cc crate gets on top of its own flags when compiling for the
+// bundled libraries, as cflags() does for configure. The cc crate compiles
+// position independent code already, which PIE executables link as well.
+fn cc_flags() -> Vec<String> {
+    FILE_PREFIX_MAP.iter().cloned()
+        .chain(build_config::sanitize_flag())
+        .chain(build_config::lto_flags())
+        .chain(build_config::hardening_flags())
+        .collect()
+}
+
+// Builds newt or slang without configure and make, see cc_build.rs. Only
+// newt links to the libraries in `libs`.
+fn build_with_cc(package: &str, version: &str, source: Option<&Path>,
+                 out_dir: &Path, libs: &[Library])
+    -> Result<Library, BuildError> {
+    let version_name = &format!("{}-{}", package, version);
+    let src_dir = &out_dir.join("build").join(version_name);
+
+    let (origin, source_line) = origin(package, version, source)?;
+    let (patches, patches_line) = find_patches(package)?;
+
+    let flags = cc_flags();
+    let target = build_config::rust_target();
+    let cc = build_config::cc_command().to_string_lossy().into_owned();
+    let cflags = cflags().replace(&*out_dir.to_string_lossy(), cache::OUT_DIR);
+    let source_date_epoch = env_var("SOURCE_DATE_EPOCH").unwrap_or_default();
+    let strip_debug = build_config::strip_debug();
+    let strip_line = if strip_debug { "debug" } else { "" };
+    let include_paths: Vec<PathBuf> = libs.iter()
+        .flat_map(|lib| lib.include_paths.iter().cloned())
+        .collect();
+
+    let cache = cache::dir().map(|dir| {
+        let includes = format!("{:?}", include_paths);
+        let gpm = cfg!(feature = "gpm").to_string();
+        let key = cache::key(&[
+            package, version, &source_line, &patches_line, &target, &cc,
+            &cflags, "cc", &includes, &gpm, &source_date_epoch, strip_line
+        ]);
+        (dir, format!("{}-{}", version_name, key))
+    });
+    let _lock = match cache {
+        Some((ref dir, ref name)) => Some(cache::lock(dir, name)
+            .map_err(|e| BuildError::new(package, Phase::Prepare, e))?),
+        None => None
+    };
+    let install_prefix = match cache {
+        Some((ref dir, ref name)) => dir.join(name),
+        None => out_dir.join("install").join(version_name)
+    };
+
+    let stamp = Stamp::new(&install_prefix.join(STAMP_FILE), &[
+        ("package", package),
+        ("version", version),
+        ("source", &source_line),
+        ("patches", &patches_line),
+        ("target", &target),
+        ("cc", &cc),
+        ("cflags", &cflags),
+        ("builder", "cc"),
+        ("source_date_epoch", &source_date_epoch),
+        ("strip", strip_line)
+    ]);
+
+    deps_report::built(package, "cc", None);
+    let mut timings = Timings::new(package, version);
+    if !stamp.is_current() {
+        stamp.remove();
+        clear_log(package);
+        let _ = fs::remove_dir_all(&install_prefix);
+        let start = Instant::now();
+        unpack(package, &origin, &patches, out_dir, src_dir)?;
+        timings.record("extract", start);
+
+        let start = Instant::now();
+        match package {
+            "newt" => cc_build::newt(src_dir, &install_prefix, version,
+                                     &include_paths, &flags),
+            "slang" => cc_build::slang(src_dir, &install_prefix, &flags),
+            _ => panic!("Unexpected package requested to be built: {}", package)
+        }.map_err(|e| BuildError::new(package, Phase::Make, e))?;
+        timings.record("compile", start);
+
+        let start = Instant::now();
+        if strip_debug {
+            strip_archives(package, &install_prefix.join("lib"))?;
+        }
+        artifacts::check(package, &install_prefix, None)
+            .map_err(|e| BuildError::new(package, Phase::Make, e))?;
+
+        stamp.write().map_err(|e| {
+            BuildError::new(package, Phase::Make,
+                            format!("unable to write build stamp: {}", e))
+        })?;
+        timings.record("install", start);
+    }
+    timings.report();
+
+    if !build_config::keep_build() {
+        let _ = fs::remove_dir_all(src_dir);
+    }
+
+    let mut library = Library {
+        include_paths: vec![install_prefix.join("include")],
+        link_paths: vec![install_prefix.join("lib")],
+        libs: vec![package.to_string()],
+        version: Some(version.to_string()),
+        root: Some(install_prefix.clone()),
+        requires: Vec::new()
+    };
+    if package == "slang" {
+        library.libs.extend(cc_build::slang_libs());
+    }
+    for lib in libs.iter() {
+        library.link_paths.extend(lib.link_paths.iter().cloned());
+        library.libs.extend(lib.libs.iter().cloned());
+    }
+    Ok(library)
+}
+
+// popt or slang from pkg-config for the `system-deps` feature. Its .pc
+// directory is searched when probing the bundled newt, as libnewt.pc
+// requires slang.
+fn find_system_dep(package: &str, statik: bool,
+                   pkg_config_paths: &mut Vec<PathBuf>)
+    -> Result<Library, BuildError> {
+    let lib = library::probe_system(package, None, statik)
+        .map_err(|e| BuildError::new(package, Phase::Probe, e))?;
+    lib.emit_cargo_metadata(statik);
+
+    if let Ok(dir) = library::system_variable(package, "pcfiledir") {
+        pkg_config_paths.push(PathBuf::from(dir));
+    }
+    Ok(lib)
+}
+
+// Check for every program the bundled build is going to run up front, so
+// that all of the missing ones are reported together.
+fn preflight() -> Result<(), BuildError> {
+    let mut missing = Vec::new();
+    let mut require = |found: bool, tool, reason| {
+        if !found {
+            missing.push(Prerequisite { tool, reason });
+        }
+    };
+
+    require(preflight::runs(build_config::compiler().path()), "cc",
+            "compiles the bundled libraries");
+    if cfg!(feature = "shared") {
+        require(MAKE.is_some(), "make",
+                "GNU Make, builds libnewt.so for the `shared` feature");
+    }
+    if !use_cc_build() {
+        if cfg!(windows) {
+            require(preflight::runs("sh"), "sh",
+                    "runs the configure scripts");
+        }
+        require(preflight::runs(build_config::pkg_config_command()),
+                "pkg-config", "reads the .pc files of the bundled libraries");
+    } else if cfg!(feature = "system-deps") {
+        require(preflight::runs(build_config::pkg_config_command()),
+                "pkg-config", "finds the system popt and slang");
+    }
+    if cfg!(feature = "download-sources") {
+        require(preflight::runs("curl"), "curl", "downloads the sources");
+    }
+
+    let mut packages: Vec<&str> = bundled_deps().iter()
+        .map(|&(package, _)| package)
+        .collect();
+    packages.push("newt");
+    let has_patches = packages.iter()
+        .any(|package| patches::find(package).is_ok_and(|p| !p.is_empty()));
+    if has_patches {
+        require(preflight::runs("patch"), "patch",
+                "applies the patches to the sources");
+    }
+
+    match preflight::report(&missing) {
+        Some(message) => Err(BuildError::new("newt", Phase::Prepare, message)),
+        None => Ok(())
+    }
+}
+
+// The tree to build `package` from and the version in it: for newt
+// NEWT_SYS_SOURCE_DIR, then a checked out submodule in vendor/src unless
+// NEWT_SYS_NEWT_VERSION asks for a release. Without one, the release
+// `version` is built from its archive.
+fn package_source(package: &str, version: &str)
+    -> Result<(Option<PathBuf>, String), BuildError> {
+    let pinned = package == "newt" &&
+                 env_var("NEWT_SYS_NEWT_VERSION")
+                     .is_some_and(|version| !version.is_empty());
+    let source = match source_dir::newt().filter(|_| package == "newt") {
+        Some(dir) => Some(dir),
+        None if pinned => None,
+        None => source_dir::submodule(package)
+    };
+
+    match source {
+        Some(dir) => {
+            let version = source_dir::version(package, &dir)
+                .map_err(|e| BuildError::new(package, Phase::Prepare, e))?;
+            Ok((Some(dir), version))
+        },
+        None => Ok((None, version.to_string()))
+    }
+}
+
+// Whether popt is built. libnewt itself doesn't use it, only whiptail
+// does, so the cc build and the `no-popt` feature leave it out.
+fn needs_popt() -> bool {
+    !use_cc_build() && !cfg!(feature = "no-popt")
+}
+
+// The libraries newt is built against.
+fn bundled_deps() -> Vec<(&'static str, &'static str)> {
+    let mut deps = vec![("popt", POPT_VERSION), ("slang", SLANG_VERSION)];
+    if !needs_popt() {
+        deps.remove(0);
+    }
+    deps
+}
+
+// What keeps `package` from being installed from the bundle cache, whose
+// bundles only have the libraries as the crate builds them by default.
+#[cfg(feature = "bundle-cache")]
+fn prebuilt_blocker(package: &str, source: Option<&Path>)
+    -> Result<Option<&'static str>, BuildError> {
+    let (patches, _) = find_patches(package)?;
+    let blocker = if source.is_some() {
+        Some("source trees (NEWT_SYS_SOURCE_DIR or vendor/src)")
+    } else if !patches.is_empty() {
+        Some("NEWT_SYS_PATCH_DIR")
+    } else if !configure_args(package)?.is_empty() {
+        Some("configure arguments")
+    } else if build_config::sanitize_flag().is_some() {
+        Some("sanitizers")
+    } else if build_config::lto() {
+        Some("NEWT_SYS_LTO")
+    } else if build_config::hardening() {
+        Some("NEWT_SYS_HARDENING")
+    } else if build_config::misc_terminfo_dirs().is_some() {
+        Some("NEWT_SYS_TERMINFO_DIRS")
+    } else if use_cc_build() {
+        Some("the `cc-build` feature")
+    } else if cfg!(feature = "shared") {
+        Some("the `shared` feature")
+    } else if cfg!(feature = "whiptail") {
+        Some("the `whiptail` feature")
+    } else if cfg!(feature = "gpm") {
+        Some("the `gpm` feature")
+    } else if cfg!(feature = "nls") {
+        Some("the `nls` feature")
+    } else if cfg!(feature = "slang-minimal") {
+        Some("the `slang-minimal` feature")
+    } else if !cfg!(feature = "utf8") {
+        Some("disabling the `utf8` feature")
+    } else {
+        None
+    };
+    Ok(blocker)
+}
+
+// Install `package` from the bundle cache if it has a bundle for the
+// target and the build.
+#[cfg(feature = "bundle-cache")]
+fn install_prebuilt(package: &str, version: &str, source: Option<&Path>,
+                    out_dir: &Path, pkg_config_paths: &mut Vec<PathBuf>)
+    -> Result<Option<Library>, BuildError> {
+    if let Some(blocker) = prebuilt_blocker(package, source)? {
+        println!("cargo:warning=the bundle cache doesn't cover {}, building \
+                  {} from source", blocker, package);
+        return Ok(None);
+    }
+
+    let install_prefix = out_dir.join("prebuilt")
+        .join(format!("{}-{}", package, version));
+    if !prebuilt::install(package, version, out_dir, &install_prefix)? {
+        return Ok(None);
+    }
+    deps_report::built(package, "prebuilt", None);
+    probe_install(package, version, &install_prefix, pkg_config_paths)
+        .map(Some)
+}
+
+#[cfg(not(feature = "bundle-cache"))]
+fn install_prebuilt(_package: &str, _version: &str, _source: Option<&Path>,
+                    _out_dir: &Path, _pkg_config_paths: &mut Vec<PathBuf>)
+    -> Result<Option<Library>, BuildError> {
+    Ok(None)
+}
+
+fn build_package(package: &str, version: &str, source: Option<&Path>,
+                 out_dir: &Path, libs: &[Library],
+                 pkg_config_paths: &mut Vec<PathBuf>)
+    -> Result<Library, BuildError> {
+    if let Some(library) = install_prebuilt(package, version, source,
+                                            out_dir, pkg_config_paths)? {
+        return Ok(library);
+    }
+
+    if use_cc_build() {
+        build_with_cc(package, version, source, out_dir, libs)
+    } else {
+        build(package, version, source, out_dir, libs, pkg_config_paths)
+    }
+}
+
+// Have `cmd` put its temporary files in build_config::temp_dir().
+fn confine_temp_files(cmd: &mut Command) -> &mut Command {
+    for var in build_config::temp_vars() {
+        cmd.env(var, build_config::temp_dir());
+    }
+    cmd
+}
+
+fn build_libs(statik: bool) -> Result<Library, BuildError> {
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let mut libraries: Vec<Library> = Vec::new();
+    let mut pkg_config_paths: Vec<PathBuf> = Vec::new();
+
+    // The bundled popt and slang are hidden inside libnewt.a where
+    // possible, so they can't clash with another copy in the same binary.
+    // objcopy can't hide the symbols of LTO objects.
+    let localize = !cfg!(feature = "shared") &&
+                   !cfg!(feature = "system-deps") &&
+                   !build_config::lto() &&
+                   symbols::can_localize();
+
+    if let Some(reason) = cc_build_reason() {
+        let needs = if cfg!(feature = "whiptail") {
+            Some("the `whiptail` feature needs newt's Makefile")
+        } else if cfg!(feature = "nls") {
+            Some("the `nls` feature needs newt's configure script and \
+                  Makefile")
+        } else if cfg!(feature = "shared") {
+            Some("the `shared` feature needs newt's Makefile")
+        } else {
+            None
+        };
+        if let Some(needs) = needs {
+            return Err(BuildError::new("newt", Phase::Prepare,
+                format!("{}, which isn't used {}", needs, reason)));
+        }
+    }
+
+    if cfg!(feature = "no-popt") &&
+       (cfg!(feature = "whiptail") || cfg!(feature = "nls")) {
+        return Err(BuildError::new("newt", Phase::Prepare,
+            "the `no-popt` feature leaves out whiptail, which the \
+             `whiptail` and `nls` features need"));
+    }
+
+    let tmp_dir = build_config::temp_dir();
+    fs::create_dir_all(&tmp_dir).map_err(|e| {
+        BuildError::new("newt", Phase::Prepare, format!(
+            "unable to create {}: {}", tmp_dir.display(), e))
+    })?;
+    preflight()?;
+
+    for &(package, version) in bundled_deps().iter() {
+        let library = if cfg!(feature = "system-deps") {
+            find_system_dep(package, statik, &mut pkg_config_paths)?
+        } else {
+            let (source, version) = package_source(package, version)?;
+            let library = build_package(package, &version, source.as_deref(),
+                                        &out_dir, &[],
+                                        &mut pkg_config_paths)?;
+            if !cfg!(feature = "shared") && !localize {
+                library.emit_cargo_metadata(true);
+            }
+            library
+        };
+        library.emit_package_metadata(package);
+        let link = if !cfg!(feature = "system-deps") ||
+                      (statik && library.links_static(package)) {
+            "static"
+        } else {
+            "dynamic"
+        };
+        deps_report::record(package, &library, link);
+        libraries.push(library);
+    }
+
+    let (source, version) = package_source("newt", &newt_version()?)?;
+    let mut library = build_package("newt", &version, source.as_deref(),
+                                    &out_dir, &libraries,
+                                    &mut pkg_config_paths)?;
+
+    deps_report::record("newt", &library, if cfg!(feature = "shared") {
+        "dynamic"
+    } else {
+        "static"
+    });
+
+    if cfg!(feature = "whiptail") {
+        if let Some(ref root) = library.root {
+            println!("cargo:whiptail={}",
+                     root.join("bin").join(exe_name("whiptail")).display());
+        }
+    }
+
+    if cfg!(feature = "nls") {
+        if let Some(ref root) = library.root {
+            println!("cargo:locale={}", root.join("share/locale").display());
+        }
+    }
+
+    if cfg!(feature = "shared") {
+        emit_shared_metadata(&library);
+        return Ok(library);
+    }
+
+    if localize {
+        localize_deps(&mut library, &libraries, &out_dir)?;
+    }
+
+    // pkg-config has already linked the system popt and slang.
+    if cfg!(feature = "system-deps") {
+        library.libs.retain(|lib| {
+            !libraries.iter().any(|dep| dep.libs.contains(lib))
+        });
+    }
+    library.emit_cargo_metadata(true);
+    Ok(library)
+}
+
+// Replace libnewt.a with one containing the popt and slang code it uses,
+// leaving only the system libraries slang needs to be linked separately.
+fn localize_deps(newt: &mut Library, deps: &[Library], out_dir: &Path)
+    -> Result<(), BuildError> {
+    let archive = |lib: &Library, name: &str| {
+        lib.link_paths.iter()
+            .map(|path| path.join(format!("lib{}.a", name)))
+            .find(|path| path.is_file())
+    };
+
+    let newt_archive = archive(newt, "newt").ok_or_else(|| {
+        BuildError::new("newt", Phase::Make, "libnewt.a wasn't installed")
+    })?;
+    let dep_archives: Vec<PathBuf> = ["popt", "slang"].iter()
+        .filter_map(|name| deps.iter().find_map(|lib| archive(lib, name)))
+        .collect();
+
+    let link_dir = symbols::localize(&newt_archive, &dep_archives, out_dir)
+        .map_err(|e| BuildError::new("newt", Phase::Make, e))?;
+    newt.link_paths.insert(0, link_dir);
+    newt.libs.retain(|lib| lib != "popt" && lib != "slang");
+    Ok(())
+}
+
+// Link the bundled libnewt.so, which has the bundled slang linked into
+// it. Binaries shipping it next to themselves can use the `rpath`
+// metadata as their rpath.
+fn emit_shared_metadata(newt: &Library) {
+    if let Some(ref root) = newt.root {
+        println!("cargo:rustc-link-search=native={}",
+                 root.join("lib").display());
+    }
+    println!("cargo:rustc-link-lib=dylib=newt");
+
+    let origin = if build_config::is_darwin() {
+        "@executable_path"
+    } else {
+        "$ORIGIN"
+    };
+    println!("cargo:rpath={}", origin);
+}
+
+// Linking a system libnewt doesn't need a C compiler otherwise, so without
+// one NEWT_COLORSET_CUSTOM is computed in Rust instead of by the C shim.
+fn build_c(lib: &Library) {
+    println!("cargo:rustc-check-cfg=cfg(newt_sys_no_c_shim)");
+    println!("cargo:rerun-if-changed=src/colorset_custom.c");
+    if !preflight::runs(build_config::compiler().path()) {
+        println!("cargo:rustc-cfg=newt_sys_no_c_shim");
+        return;
+    }
+
+    // Compiled like the bundled libraries, so a cross build's archiver and
+    // a sanitized, LTO or hardened build's flags apply to it as well.
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let mut build = cc_build::target_build(&cc_flags());
+    build.file("src/colorset_custom.c")
+        .out_dir(&out_dir)
+        .cargo_metadata(false);
+    for path in lib.include_paths.iter() {
+        build.include(path);
+    }
+    cc_build::compile(&build, "newt-rs", &out_dir).unwrap_or_else(|e| {
+        fail(BuildError::new("newt", Phase::Make,
+                             format!("unable to compile the C shim: {}", e)))
+    });
+    println!("cargo:rustc-link-search=native={}", out_dir.display());
+    println!("cargo:rustc-link-lib=static=newt-rs");
+}
+
+fn find_system(vendored: bool, statik: bool) -> Result<Library, String> {
+    if vendored {
+        return Err(String::from("a vendored build was requested"));
+    }
+
+    if let Some(lib) = library::from_env_dirs(statik) {
+        return emit_checked(lib, statik);
+    }
+
+    // NEWT_PREFIX's .pc files come before any others. Without a libnewt.pc
+    // (or newt.pc) the library is looked for in the prefix directly.
+    if let Some(prefix) = library::newt_prefix() {
+        let dirs = library::prefix_pkg_config_dirs(&prefix);
+        if !dirs.iter().any(|dir| library::pc_name_in(dir).is_some()) {
+            return emit_checked(library::from_prefix(&prefix, statik)?,
+                                statik);
+        }
+    }
+
+    if build_config::is_cross() && !pkg_config_targets_cross() {
+        let target = build_config::rust_target();
+        return find_vcpkg(format!(
+            "pkg-config isn't set up for cross-compiling to {0} and would \
+             find the host's libnewt; set PKG_CONFIG_SYSROOT_DIR_{0} or \
+             PKG_CONFIG_PATH_{0} to use the target's", target));
+    }
+
+    let name = library::system_pc_name().unwrap_or(library::PC_NAMES[0]);
+
+    // A static libnewt needs everything slang links to as well, which only
+    // `pkg-config --static` knows.
+    if statik {
+        let mut lib = match library::probe_static(name, "newt",
+                                                  NEWT_MIN_VERSION) {
+            Ok(lib) => lib,
+            Err(e) => {
+                let lib = library::find_archive().ok_or_else(|| {
+                    format!("static linking was requested: {}", e)
+                })?;
+                return emit_checked(lib, true);
+            }
+        };
+        lib.requires = library::system_requires(name);
+        let lib = emit_checked(lib, true)?;
+        println!("cargo:pkg_config={}", name);
+        return Ok(lib);
+    }
+
+    let mut lib = match library::probe_system(name, Some(NEWT_MIN_VERSION),
+                                              false) {
+        Ok(lib) => lib,
+        Err(e) => return find_vcpkg(e)
+    };
+    lib.requires = library::system_requires(name);
+    let lib = emit_checked(lib, false)?;
+    println!("cargo:pkg_config={}", name);
+    Ok(lib)
+}
+
+// Link the system `lib` once its headers are known to be usable, nothing
+// is linked otherwise and the bundled libraries are built instead.
+fn emit_checked(lib: Library, statik: bool) -> Result<Library, String> {
+    check_headers(&lib)?;
+    lib.emit_cargo_metadata(statik);
+    Ok(lib)
+}
+
+// Compile a file including newt.h with the include paths found for `lib`,
+// so that a half-installed development package, its headers missing or not
+// matching the library, isn't used. Without a C compiler there is nothing
+// to check, nothing else is compiled against them.
+fn check_headers(lib: &Library) -> Result<(), String> {
+    if !preflight::runs(build_config::compiler().path()) {
+        return Ok(());
+    }
+
+    let dir = PathBuf::from(env::var_os("OUT_DIR").unwrap())
+        .join("header-check");
+    let source = dir.join("newt_h.c");
+    fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(&source, HEADER_CHECK))
+        .map_err(|e| format!("unable to write {}: {}", source.display(), e))?;
+
+    let mut build = cc_build::target_build(&[]);
+    build.file(&source)
+        .out_dir(&dir)
+        .cargo_metadata(false)
+        .cargo_warnings(false)
+        .warnings(false);
+    for path in lib.include_paths.iter() {
+        build.include(path);
+    }
+    cc_build::compile(&build, "newt-header-check", &dir).map_err(|e| {
+        format!("the system libnewt's newt.h can't be compiled against: {}",
+                e)
+    })
+}
+
+// Only trust pkg-config in a cross build if it has been pointed at the
+// target's libraries, PKG_CONFIG_ALLOW_CROSS alone still finds the host's.
+fn pkg_config_targets_cross() -> bool {
+    if build_config::target_env_var("PKG_CONFIG_ALLOW_CROSS")
+        .or_else(|| env_var("PKG_CONFIG_ALLOW_CROSS"))
+        .is_some_and(|val| val == "0") {
+        return false;
+    }
+
+    build_config::in_cross_container() ||
+        env_var("PKG_CONFIG_SYSROOT_DIR").is_some() ||
+        ["PKG_CONFIG", "PKG_CONFIG_PATH", "PKG_CONFIG_LIBDIR",
+         "PKG_CONFIG_SYSROOT_DIR"].iter()
+            .any(|var| build_config::target_env_var(var).is_some())
+}
+
+#[cfg(feature = "vcpkg")]
+fn find_vcpkg(pkg_config_error: String) -> Result<Library, String> {
+    vcpkg::Config::new()
+        .find_package("newt")
+        .map(Library::from)
+        .map_err(|e| format!("{}\nvcpkg: {}", pkg_config_error, e))
+}
+
+#[cfg(not(feature = "vcpkg"))]
+fn find_vcpkg(pkg_config_error: String) -> Result<Library, String> {
+    Err(pkg_config_error)
+}
+
+// Point lib.rs at the newest set of bindings the linked newt supports, or
+// the newest overall if its version is unknown.
+fn select_bindings(lib: &Library) {
+    let file = match lib.version {
+        Some(ref version) => BINDINGS.iter().rev()
+            .find(|&&(min, _)| {
+                version::compare(version, min) != Ordering::Less
+            })
+            .unwrap_or(&BINDINGS[0]).1,
+        None => BINDINGS[BINDINGS.len() - 1].1
+    };
+
+    let path = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap())
+        .join("src/bindings").join(file);
+    println!("cargo:rustc-env=NEWT_SYS_BINDINGS={}", path.display());
+}
+
+fn release_cfg(release: &str) -> String {
+    format!("newt_{}", release.replace('.', "_"))
+}
+
+// Emit a cfg for every known release up to the linked version, all of them
+// if the version is unknown.
+fn emit_version_cfgs(lib: &Library) {
+    let cfgs: Vec<String> = NEWT_RELEASES.iter()
+        .map(|release| release_cfg(release))
+        .collect();
+    println!("cargo:rustc-check-cfg=cfg({})", cfgs.join(", "));
+
+    for (release, cfg) in NEWT_RELEASES.iter().zip(cfgs.iter()) {
+        let supported = match lib.version {
+            Some(ref version) =>
+                version::compare(version, release) != Ordering::Less,
+            None => true
+        };
+        if supported {
+            println!("cargo:rustc-cfg={}", cfg);
+        }
+    }
+}
+
+// Say which libnewt is linked and how. Building the bundled libraries
+// takes a while and surprises those who expected a system libnewt, so the
+// reason a system one wasn't used comes along.
+fn report_linkage(lib: &Library, linked_static: bool,
+                  fallback: Option<&str>) {
+    let version = lib.version.as_deref().unwrap_or("of unknown version");
+    let linkage = if linked_static { "statically" } else { "dynamically" };
+    match fallback {
+        Some(reason) => {
+            // The first line of the error, with what it introduces.
+            let mut lines = reason.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty());
+            let mut summary = lines.next().unwrap_or_default().to_string();
+            while summary.ends_with(':') {
+                match lines.next() {
+                    Some(line) => summary = format!("{} {}", summary, line),
+                    None => break
+                }
+            }
+            println!("cargo:warning=linking the bundled libnewt {} {} \
+                      instead of a system one: {}", version, linkage,
+                     summary);
+        },
+        None => {
+            let root = lib.root.as_ref()
+                .map(|root| format!(" from {}", root.display()))
+                .unwrap_or_default();
+            println!("cargo:warning=linking the system libnewt {}{} {}",
+                     version, root, linkage);
+        }
+    }
+}
+
+fn fail(e: BuildError) -> ! {
+    eprintln!("{}", e);
+    process::exit(1);
+}
+
+// newt and slang need POSIX terminal APIs, MSVC targets can only link a
+// prebuilt libnewt.
+fn msvc_error(detail: &str) -> BuildError {
+    BuildError::new("newt", Phase::Prepare, format!(
+        "the bundled libraries can't be built for {}, newt and slang need \
+         POSIX terminal APIs that MSVC doesn't provide. Link a prebuilt \
+         libnewt with NEWT_LIB_DIR or the `vcpkg` feature, or use a \
+         windows-gnu target from MSYS2 or Cygwin{}",
+        build_config::rust_target(), detail))
+}
+
+fn main() {
+    // NEWT_STATIC=0 overrides a `static` feature enabled by another crate.
+    let statik = build_config::env_bool("NEWT_STATIC")
+        .unwrap_or(cfg!(feature = "static"));
+    let source_dir = source_dir::newt();
+    let pinned = env_var("NEWT_SYS_NEWT_VERSION")
+        .is_some_and(|version| !version.is_empty());
+    let vendored = cfg!(feature = "vendored") ||
+                   env_var("NEWT_NO_PKG_CONFIG").is_some() ||
+                   source_dir.is_some() || pinned;
+    let system_only = cfg!(feature = "system");
+
+    if !build_config::has_terminal() {
+        fail(BuildError::new("newt", Phase::Prepare, format!(
+            "{} isn't supported, newt is a terminal UI library and the \
+             target has no terminal for it to use",
+            build_config::rust_target())));
+    }
+
+    if build_config::is_msvc() && vendored {
+        fail(msvc_error(""));
+    }
+
+    if system_only && cfg!(feature = "vendored") {
+        fail(BuildError::new("newt", Phase::Prepare,
+             "the `system` and `vendored` features are mutually exclusive"));
+    }
+
+    if statik && cfg!(feature = "shared") {
+        fail(BuildError::new("newt", Phase::Prepare,
+             "the `shared` feature can't be combined with static linking"));
+    }
+
+    if cfg!(feature = "cc-build") && cfg!(feature = "shared") {
+        fail(BuildError::new("newt", Phase::Prepare,
+             "the `cc-build` and `shared` features are mutually exclusive"));
+    }
+
+    if system_only && source_dir.is_some() {
+        fail(BuildError::new("newt", Phase::Prepare,
+             "NEWT_SYS_SOURCE_DIR can't be used with the `system` feature"));
+    }
+
+    if pinned && (system_only || source_dir.is_some()) {
+        fail(BuildError::new("newt", Phase::Prepare,
+             "NEWT_SYS_NEWT_VERSION can't be used with the `system` feature \
+              or NEWT_SYS_SOURCE_DIR"));
+    }
+
+    // `vendored` always builds the bundled libraries. `static` only asks
+    // for static linkage, which needs a system libnewt.a. Without one it
+    // falls back to building the bundled libraries, or with `system` to
+    // linking the system libnewt dynamically.
+    let system = find_system(vendored, statik)
+        .map(|lib| (lib, statik))
+        .or_else(|e| {
+            if system_only && statik {
+                find_system(vendored, false).map(|lib| (lib, false))
+            } else {
+                Err(e)
+            }
+        });
+    let (lib, bundled, linked_static, fallback) = match system {
+        Ok((lib, statik)) => {
+            let linked_static = statik && lib.is_static("newt");
+            deps_report::record("newt", &lib, if linked_static {
+                "static"
+            } else {
+                "dynamic"
+            });
+            (lib, false, linked_static, None)
+        },
+        Err(e) if system_only => {
+            fail(BuildError::new("newt", Phase::Probe, format!(
+                "the `system` feature is enabled but no usable system \
+                 libnewt >= {} was found: {}", NEWT_MIN_VERSION, e)))
+        },
+        Err(e) if build_config::is_msvc() =>
+            fail(msvc_error(&format!(" (no libnewt was found: {})", e))),
+        Err(e) => (build_libs(statik).unwrap_or_else(|e| fail(e)), true,
+                   !cfg!(feature = "shared"), Some(e))
+    };
+    report_linkage(&lib, linked_static, fallback.as_deref());
+    lib.emit_dep_metadata();
+    select_bindings(&lib);
+    emit_version_cfgs(&lib);
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let (popt, slang) = if bundled && !cfg!(feature = "system-deps") {
+        let bundled_version = |package, version| {
+            package_source(package, version)
+                .map(|(_, version)| version)
+                .unwrap_or_else(|e| fail(e))
+        };
+        let popt = if needs_popt() {
+            Some(bundled_version("popt", POPT_VERSION))
+        } else {
+            None
+        };
+        (popt, Some(bundled_version("slang", SLANG_VERSION)))
+    } else if bundled {
+        (library::system_version("popt"), library::system_version("slang"))
+    } else {
+        // Only what libnewt's own .pc file says it was built against, a
+        // popt or slang found on their own may have nothing to do with it.
+        let required = |package: &str| {
+            lib.requires.iter()
+                .find(|(name, _)| name == package)
+                .map(|(_, version)| version.clone())
+        };
+        (required("popt"), required("slang"))
+    };
+    version::write_module(&out_dir, lib.version.as_deref(), popt.as_deref(),
+                          slang.as_deref())
+        .unwrap_or_else(|e| {
+            fail(BuildError::new("newt", Phase::Prepare,
+                 format!("unable to write the version module: {}", e)))
+        });
+    build_info::write_module(&out_dir, linked_static, bundled)
+        .unwrap_or_else(|e| {
+            fail(BuildError::new("newt", Phase::Prepare,
+                 format!("unable to write the build_info module: {}", e)))
+        });
+    deps_report::write(&out_dir, linked_static, bundled)
+        .unwrap_or_else(|e| {
+            fail(BuildError::new("newt", Phase::Prepare,
+                 format!("unable to write the dependency report: {}", e)))
+        });
+
+    #[cfg(feature = "buildtime-bindgen")]
+    {
+        bindings::generate(&lib, &out_dir).unwrap_or_else(|e| {
+            fail(BuildError::new("newt", Phase::Bindgen, e))
+        });
+    }
+    build_c(&lib);
+}
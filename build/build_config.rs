@@ -0,0 +1,609 @@
+use lazy_static::lazy_static;
+
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path,PathBuf};
+use std::process::{Command,Stdio};
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref TRACKED_ENV: Mutex<Vec<String>> = Mutex::new(Vec::new());
+}
+
+pub fn rerun_if_env_changed(name: &str) {
+    let mut tracked = TRACKED_ENV.lock().unwrap();
+    if !tracked.iter().any(|var| var == name) {
+        println!("cargo:rerun-if-env-changed={}", name);
+        tracked.push(name.to_string());
+    }
+}
+
+pub fn env_var(name: &str) -> Option<String> {
+    rerun_if_env_changed(name);
+    env::var(name).ok()
+}
+
+// The target specific forms of `base`, checked in the same order as the cc
+// and pkg-config crates do: <base>_<target>, <base>_<target with
+// underscores> and TARGET_<base> (HOST_<base> for native builds).
+pub fn target_env_var(base: &str) -> Option<String> {
+    let target = rust_target();
+    let kind = if is_cross() { "TARGET" } else { "HOST" };
+    env_var(&format!("{}_{}", base, target))
+        .or_else(|| env_var(&format!("{}_{}", base, target.replace('-', "_"))))
+        .or_else(|| env_var(&format!("{}_{}", kind, base)))
+}
+
+// `base` as the cc crate reads it: a target specific form if set, the plain
+// variable otherwise.
+pub fn target_var(base: &str) -> Option<String> {
+    target_env_var(base).or_else(|| env_var(base))
+}
+
+// A yes/no variable: empty, 0, false, no and off are no, anything else is
+// yes. None if it isn't set.
+pub fn env_bool(name: &str) -> Option<bool> {
+    env_var(name).map(|val| {
+        let val = val.trim().to_ascii_lowercase();
+        !["", "0", "false", "no", "off"].contains(&val.as_str())
+    })
+}
+
+fn env_flag(name: &str) -> bool {
+    env_bool(name).unwrap_or(false)
+}
+
+// Where configure, make and the compiler put their temporary files, under
+// OUT_DIR rather than /tmp, which sandboxed builders may not allow writing.
+pub fn temp_dir() -> PathBuf {
+    PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("tmp")
+}
+
+// The variables pointing a command at temp_dir().
+pub fn temp_vars() -> &'static [&'static str] {
+    if cfg!(windows) {
+        &["TMPDIR", "TMP", "TEMP"]
+    } else {
+        &["TMPDIR"]
+    }
+}
+
+pub fn verbose() -> bool {
+    env_flag("NEWT_SYS_VERBOSE")
+}
+
+// cargo was told not to touch the network, so neither should downloads.
+#[cfg(any(feature = "download-sources", feature = "bundle-cache"))]
+pub fn offline() -> bool {
+    env_flag("CARGO_NET_OFFLINE")
+}
+
+// Report how long each phase of the bundled builds took.
+pub fn timings() -> bool {
+    env_flag("NEWT_SYS_TIMINGS")
+}
+
+// Keep the bundled libraries' build trees for debugging.
+pub fn keep_build() -> bool {
+    env_flag("NEWT_SYS_KEEP_BUILD")
+}
+
+// Drop the debug info of the bundled static libraries. Not for LTO
+// objects, whose IR still refers to the debug sections.
+pub fn strip_debug() -> bool {
+    env_flag("NEWT_SYS_STRIP_DEBUG") && !lto()
+}
+
+// Whether the bundled libraries end up in a static PIE, which needs all of
+// their objects position independent. rustc links those for Linux targets
+// with the crt-static target feature.
+pub fn static_pie() -> bool {
+    env_bool("NEWT_SYS_STATIC_PIE").unwrap_or_else(|| {
+        let crt_static = env::var("CARGO_CFG_TARGET_FEATURE")
+            .is_ok_and(|features| {
+                features.split(',').any(|feature| feature == "crt-static")
+            });
+        crt_static && rust_target().contains("-linux-")
+    })
+}
+
+// Compile the bundled libraries with the hardening flags distributions
+// require of packaged code.
+pub fn hardening() -> bool {
+    env_flag("NEWT_SYS_HARDENING")
+}
+
+// The hardening flags other than -fPIE, which only goes to code that isn't
+// position independent already. _FORTIFY_SOURCE needs optimization, glibc
+// warns about it otherwise.
+pub fn hardening_flags() -> Vec<String> {
+    if !hardening() {
+        return Vec::new();
+    }
+
+    let mut flags = vec![String::from("-fstack-protector-strong")];
+    if env::var("OPT_LEVEL").is_ok_and(|level| level != "0") {
+        flags.push(String::from("-D_FORTIFY_SOURCE=2"));
+    }
+    flags
+}
+
+// The -fsanitize= flag matching the sanitizers the Rust code is built with
+// (-Zsanitizer=...), or NEWT_SYS_SANITIZER, a list like address,undefined
+// passed to the compiler as is.
+pub fn sanitize_flag() -> Option<String> {
+    if let Some(sanitizers) = env_var("NEWT_SYS_SANITIZER") {
+        return Some(sanitizers)
+            .filter(|sanitizers| !sanitizers.is_empty())
+            .map(|sanitizers| format!("-fsanitize={}", sanitizers));
+    }
+
+    // Those with a C compiler counterpart of the same name.
+    let sanitizers: Vec<String> = env::var("CARGO_CFG_SANITIZE")
+        .unwrap_or_default()
+        .split(',')
+        .filter(|sanitizer| ["address", "hwaddress", "leak", "memory",
+                             "thread"].contains(sanitizer))
+        .map(String::from)
+        .collect();
+    if sanitizers.is_empty() {
+        return None;
+    }
+    Some(format!("-fsanitize={}", sanitizers.join(",")))
+}
+
+// NEWT_SYS_TERMINFO_DIRS, searched by slang ahead of its built-in terminfo
+// directories and separated by colons like TERMINFO_DIRS, as C string
+// literals for the Terminfo_Dirs array in sltermin.c.
+pub fn misc_terminfo_dirs() -> Option<String> {
+    let dirs = env_var("NEWT_SYS_TERMINFO_DIRS")?;
+    let literals: Vec<String> = dirs.split(':')
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| {
+            format!("\"{}\"", dir.replace('\\', "\\\\").replace('"', "\\\""))
+        })
+        .collect();
+    if literals.is_empty() { None } else { Some(literals.join(",")) }
+}
+
+pub fn rust_target() -> String {
+    env::var("TARGET").unwrap_or_default()
+}
+
+pub fn rust_host() -> String {
+    env::var("HOST").unwrap_or_default()
+}
+
+pub fn is_cross() -> bool {
+    rust_target() != rust_host()
+}
+
+pub fn is_musl() -> bool {
+    rust_target().split('-').any(|part| part.starts_with("musl"))
+}
+
+pub fn is_msvc() -> bool {
+    rust_target().ends_with("-msvc")
+}
+
+// newt draws on a terminal, which WebAssembly and bare metal targets don't
+// have.
+pub fn has_terminal() -> bool {
+    let target = rust_target();
+    !target.starts_with("wasm") && !target.contains("-none") &&
+        !target.ends_with("-uefi")
+}
+
+pub fn is_darwin() -> bool {
+    rust_target().ends_with("-apple-darwin")
+}
+
+pub fn is_openbsd() -> bool {
+    rust_target().ends_with("-openbsd")
+}
+
+pub fn is_bsd() -> bool {
+    ["-freebsd", "-openbsd", "-netbsd", "-dragonfly"].iter()
+        .any(|os| rust_target().ends_with(os))
+}
+
+// Prefix of the BSD package collections (ports and pkgsrc).
+pub fn local_prefix() -> Option<&'static str> {
+    if rust_target().ends_with("-netbsd") {
+        Some("/usr/pkg")
+    } else if is_bsd() {
+        Some("/usr/local")
+    } else {
+        None
+    }
+}
+
+// Where the BSD packages, Homebrew and MacPorts install their .pc files,
+// which pkg-config isn't always configured to search.
+pub fn pkg_config_dirs() -> Vec<PathBuf> {
+    let target = rust_target();
+    let dirs: &[&str] = if target.ends_with("-freebsd") ||
+                           target.ends_with("-dragonfly") {
+        &["/usr/local/libdata/pkgconfig"]
+    } else if target.ends_with("-openbsd") {
+        &["/usr/local/lib/pkgconfig"]
+    } else if target.ends_with("-netbsd") {
+        &["/usr/pkg/lib/pkgconfig"]
+    } else if is_darwin() {
+        return darwin_pkg_config_dirs();
+    } else {
+        &[]
+    };
+    dirs.iter().map(PathBuf::from).collect()
+}
+
+// Homebrew's newt, which `brew --prefix` finds even if it isn't linked into
+// the Homebrew prefix, Homebrew's prefixes on Apple silicon and Intel, and
+// MacPorts'.
+fn darwin_pkg_config_dirs() -> Vec<PathBuf> {
+    let brew = Command::new("brew")
+        .args(["--prefix", "newt"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            let prefix = String::from_utf8_lossy(&output.stdout);
+            Path::new(prefix.trim()).join("lib/pkgconfig")
+        });
+
+    brew.into_iter()
+        .chain(["/opt/homebrew/lib/pkgconfig", "/usr/local/lib/pkgconfig",
+                "/opt/local/lib/pkgconfig"].iter().map(PathBuf::from))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+// iconv lives in libiconv rather than libc on macOS and OpenBSD.
+pub fn needs_libiconv() -> bool {
+    is_darwin() || is_openbsd()
+}
+
+// gettext lives in libintl rather than libc on macOS and the BSDs.
+pub fn needs_libintl() -> bool {
+    is_darwin() || is_bsd()
+}
+
+// Paths handed to the MSYS2/Cygwin shell use forward slashes; the shell
+// and the mingw tools all accept the mixed `C:/...` form.
+pub fn shell_path(path: &str) -> String {
+    if cfg!(windows) {
+        path.replace('\\', "/")
+    } else {
+        path.to_string()
+    }
+}
+
+// MSYS2 exports MSYSTEM (MINGW64, UCRT64, CLANG64, ...) and MINGW_PREFIX in
+// its shells. Use the pkg-config of that environment so its .pc files are
+// the ones found.
+fn msys2_pkg_config() -> Option<String> {
+    env_var("MSYSTEM")?;
+    let prefix = env_var("MINGW_PREFIX")?;
+
+    let output = Command::new("cygpath").arg("-m").arg(&prefix)
+        .output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Some(format!("{}/bin/pkg-config", root))
+}
+
+lazy_static! {
+    // Finding it runs cygpath, so this is only done once.
+    static ref MSYS2_PKG_CONFIG: Option<String> = msys2_pkg_config();
+}
+
+// The pkg-config binary to run, e.g. pkgconf or a distribution's
+// <triple>-pkg-config wrapper in PKG_CONFIG_<target>. A native build in an
+// MSYS2 shell uses the pkg-config of its environment otherwise.
+pub fn pkg_config_command() -> String {
+    target_var("PKG_CONFIG")
+        .or_else(|| if is_cross() { None } else { MSYS2_PKG_CONFIG.clone() })
+        .unwrap_or_else(|| String::from("pkg-config"))
+}
+
+// Autoconf host triple for the cargo target. NEWT_SYS_AUTOCONF_HOST
+// overrides it for targets the mapping doesn't cover.
+pub fn target() -> String {
+    match env_var("NEWT_SYS_AUTOCONF_HOST") {
+        Some(host) => host,
+        None => autoconf_host(&rust_target())
+    }
+}
+
+// Autoconf triple for the machine running the build, passed as --build so
+// that configure doesn't have to guess it from a compiler that may itself
+// be a cross compiler.
+pub fn autoconf_build() -> String {
+    autoconf_host(&rust_host())
+}
+
+// Rewrite the parts of a Rust target triple that the config.sub scripts
+// shipped with popt and slang don't understand.
+fn autoconf_host(target: &str) -> String {
+    let (arch, rest) = match target.find('-') {
+        Some(idx) => (&target[..idx], &target[idx + 1..]),
+        None => return target.to_string()
+    };
+
+    let arch = if arch.starts_with("riscv64") {
+        "riscv64"
+    } else if arch.starts_with("riscv32") {
+        "riscv32"
+    } else if arch == "thumbv7neon" {
+        "armv7"
+    } else {
+        arch
+    };
+
+    let rest = match rest {
+        "linux-androideabi" if arch == "armv7" =>
+            return String::from("armv7a-linux-androideabi"),
+        "pc-windows-gnu" | "pc-windows-gnullvm" |
+        "uwp-windows-gnu" => "w64-mingw32",
+        "unknown-illumos" => "pc-solaris2.11",
+        "pc-solaris" | "sun-solaris" =>
+            return format!("{}-{}2.11", arch, rest),
+        // OpenHarmony is a musl based Linux.
+        "unknown-linux-ohos" if arch == "armv7" => "unknown-linux-musleabi",
+        "unknown-linux-ohos" => "unknown-linux-musl",
+        _ => rest
+    };
+    format!("{}-{}", arch, rest)
+}
+
+// The C compiler the cc crate would use for the cargo target, which takes
+// care of CC/CC_<target> and of picking musl-gcc, the NDK clang wrappers or
+// a prefixed cross compiler.
+pub fn compiler() -> cc::Tool {
+    cc::Build::new().get_compiler()
+}
+
+// A compiler launcher such as ccache or sccache.
+pub fn cc_wrapper() -> Option<String> {
+    env_var("NEWT_SYS_CC_WRAPPER").filter(|wrapper| !wrapper.is_empty())
+}
+
+// The compiler as a CC value for configure, keeping the arguments that
+// select the target and sysroot. NEWT_SYS_CC_WRAPPER replaces a launcher
+// the cc crate found in CC or RUSTC_WRAPPER.
+pub fn cc_command() -> OsString {
+    let compiler = compiler();
+    let env_args = cc_env_args(&compiler);
+    let mut cc = match cc_wrapper() {
+        Some(wrapper) => {
+            let mut cc = OsString::from(wrapper);
+            cc.push(" ");
+            cc.push(compiler.path());
+            cc
+        },
+        None if !compiler.cc_env().is_empty() => compiler.cc_env(),
+        None => compiler.path().as_os_str().to_owned()
+    };
+    if compiler.cc_env().is_empty() || cc_wrapper().is_some() {
+        for arg in env_args.iter() {
+            cc.push(" ");
+            cc.push(arg);
+        }
+    }
+
+    // A target given in CC wins over the LLVM triple the cc crate adds for
+    // clang-like compilers, which `zig cc` doesn't understand.
+    let own_target = env_args.iter()
+        .any(|arg| selects_target(&arg.to_string_lossy()));
+
+    let mut args = compiler.args().iter();
+    while let Some(arg) = args.next() {
+        let arg_str = arg.to_string_lossy();
+        if own_target && selects_target(&arg_str) {
+            if arg_str == "-target" {
+                args.next();
+            }
+        } else if arg_str.starts_with("--target=") ||
+                  arg_str.starts_with("--sysroot") {
+            cc.push(" ");
+            cc.push(arg);
+        } else if arg_str == "-target" || arg_str == "-isysroot" {
+            cc.push(" ");
+            cc.push(arg);
+            if let Some(value) = args.next() {
+                cc.push(" ");
+                cc.push(value);
+            }
+        }
+    }
+    cc
+}
+
+// The arguments given with the compiler in CC, such as `cc -target
+// aarch64-linux-musl` in CC="zig cc -target aarch64-linux-musl". The cc
+// crate keeps them apart from its own arguments and passes them first.
+fn cc_env_args(compiler: &cc::Tool) -> Vec<OsString> {
+    let cmd = compiler.to_command();
+    let mut args: Vec<OsString> = cmd.get_args().map(OsString::from).collect();
+
+    // A launcher runs the compiler, which is then its first argument.
+    if cmd.get_program() != compiler.path() && !args.is_empty() {
+        args.remove(0);
+    }
+    args.truncate(args.len().saturating_sub(compiler.args().len()));
+    args
+}
+
+fn selects_target(arg: &str) -> bool {
+    arg == "-target" || arg.starts_with("-target=") ||
+        arg.starts_with("--target=")
+}
+
+// cross (https://github.com/cross-rs/cross) images export CROSS_SYSROOT
+// and CROSS_TOOLCHAIN_PREFIX next to the CC_<target> variables.
+pub fn in_cross_container() -> bool {
+    cross_sysroot().is_some() || cross_toolchain_prefix().is_some()
+}
+
+pub fn cross_sysroot() -> Option<String> {
+    env_var("CROSS_SYSROOT")
+}
+
+pub fn cross_toolchain_prefix() -> Option<String> {
+    env_var("CROSS_TOOLCHAIN_PREFIX")
+}
+
+// The cross image's binutils, unless AR/RANLIB are set for the target.
+fn cross_tool(base: &str, tool: &str) -> Option<OsString> {
+    if target_var(base).is_some() {
+        return None;
+    }
+    cross_toolchain_prefix()
+        .map(|prefix| OsString::from(format!("{}{}", prefix, tool)))
+}
+
+pub fn archiver() -> OsString {
+    cross_tool("AR", "ar")
+        .or_else(|| lto_tool("AR", "ar"))
+        .unwrap_or_else(|| {
+            cc::Build::new().get_archiver().get_program().to_owned()
+        })
+}
+
+// Extra options for the configure script of `package`, from
+// NEWT_SYS_<PACKAGE>_CONFIGURE_ARGS, split into words the way a POSIX
+// shell does, quotes and backslashes included.
+pub fn configure_args(package: &str) -> Result<Vec<String>, String> {
+    let name = format!("NEWT_SYS_{}_CONFIGURE_ARGS", package.to_uppercase());
+    match env_var(&name) {
+        Some(args) => shlex::split(&args)
+            .ok_or_else(|| format!("{} has an unterminated quote or a \
+                                    trailing backslash", name)),
+        None => Ok(Vec::new())
+    }
+}
+
+// AR for configure: as set, with any arguments it comes with (AR="zig
+// ar"), or the archiver picked above.
+pub fn archiver_env() -> OsString {
+    target_var("AR").map(OsString::from).unwrap_or_else(archiver)
+}
+
+// archiver_env() as a command to run.
+pub fn archiver_command() -> Command {
+    let ar = archiver_env();
+    if Path::new(&ar).is_file() {
+        return Command::new(ar);
+    }
+
+    let ar = ar.to_string_lossy().into_owned();
+    let mut parts = ar.split_whitespace();
+    let mut cmd = Command::new(parts.next().unwrap_or("ar"));
+    cmd.args(parts);
+    cmd
+}
+
+// Compile the bundled libraries for link time optimization together with
+// the Rust code (-Clinker-plugin-lto).
+pub fn lto() -> bool {
+    env_flag("NEWT_SYS_LTO")
+}
+
+// clang has to emit LLVM bitcode for the Rust linker plugin. GCC's fat
+// objects keep machine code next to its own IR, so they still link without
+// LTO.
+pub fn lto_flags() -> Vec<String> {
+    let flags: &[&str] = if !lto() {
+        &[]
+    } else if compiler().is_like_clang() {
+        &["-flto=thin"]
+    } else {
+        &["-flto=auto", "-ffat-lto-objects"]
+    };
+    flags.iter().map(|flag| flag.to_string()).collect()
+}
+
+// Linking clang's bitcode, as configure's checks do, takes a linker with
+// LLVM's plugin.
+pub fn lto_linker_flag() -> Option<String> {
+    if lto() && !is_darwin() && compiler().is_like_clang() {
+        Some(String::from("-fuse-ld=lld"))
+    } else {
+        None
+    }
+}
+
+// An ar or ranlib that can index LTO objects, unless `base` is set: the
+// gcc-ar and gcc-ranlib wrappers next to GCC, or LLVM's for clang.
+fn lto_tool(base: &str, tool: &str) -> Option<OsString> {
+    if !lto() || target_var(base).is_some() {
+        return None;
+    }
+
+    let compiler = compiler();
+    if compiler.is_like_clang() {
+        return Some(OsString::from(format!("llvm-{}", tool)));
+    }
+    let path = compiler.path().to_string_lossy().into_owned();
+    let prefix = path.strip_suffix("gcc").unwrap_or("");
+    Some(OsString::from(format!("{}gcc-{}", prefix, tool)))
+}
+
+// objcopy for the target: OBJCOPY, the cross image's, or the one going
+// with the archiver the cc crate picked (<triple>-ar, llvm-ar).
+pub fn objcopy() -> String {
+    if let Some(tool) = cross_tool("OBJCOPY", "objcopy") {
+        return tool.to_string_lossy().into_owned();
+    }
+    if let Some(objcopy) = target_var("OBJCOPY") {
+        return objcopy;
+    }
+
+    let ar = archiver().to_string_lossy().into_owned();
+    let ar = match ar.strip_suffix("gcc-ar") {
+        Some(prefix) => format!("{}ar", prefix),
+        None => ar
+    };
+    if let Some(prefix) = ar.strip_suffix("-ar") {
+        return format!("{}-objcopy", prefix);
+    }
+    match ar.strip_suffix("ar") {
+        Some(dir) if dir.is_empty() || dir.ends_with('/') =>
+            format!("{}objcopy", dir),
+        _ => String::from("objcopy")
+    }
+}
+
+// STRIP for the target, if one is set or the cross image has one.
+pub fn strip() -> Option<OsString> {
+    cross_tool("STRIP", "strip")
+        .or_else(|| target_var("STRIP").map(OsString::from))
+}
+
+// The program and option removing the debug info from a static library:
+// STRIP, or the target's objcopy, which takes the same --strip-debug.
+// Apple's strip calls it -S.
+pub fn debug_stripper() -> (OsString, &'static str) {
+    if is_darwin() {
+        return (strip().unwrap_or_else(|| OsString::from("strip")), "-S");
+    }
+    let program = strip().unwrap_or_else(|| OsString::from(objcopy()));
+    (program, "--strip-debug")
+}
+
+// The NDK has no <triple>-ranlib, cc falls back to llvm-ranlib there.
+pub fn ranlib() -> OsString {
+    cross_tool("RANLIB", "ranlib")
+        .or_else(|| lto_tool("RANLIB", "ranlib"))
+        .unwrap_or_else(|| {
+            cc::Build::new().get_ranlib().get_program().to_owned()
+        })
+}
+
+// RANLIB for configure, like archiver_env().
+pub fn ranlib_env() -> OsString {
+    target_var("RANLIB").map(OsString::from).unwrap_or_else(ranlib)
+}
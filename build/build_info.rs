@@ -0,0 +1,37 @@
+use build_config;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// The cargo features newt-sys was built with, as cargo spells them.
+fn features() -> Vec<String> {
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(name, _)| {
+            name.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+    features
+}
+
+// Write the `build_info` module included by lib.rs to
+// `{out_dir}/build_info.rs`.
+pub fn write_module(out_dir: &Path, statik: bool, vendored: bool)
+    -> io::Result<()> {
+    let module = format!(
+        "/// Whether libnewt was linked statically.\n\
+         pub const STATIC: bool = {};\n\
+         /// Whether libnewt was built from the bundled sources rather than \
+         found on the system.\n\
+         pub const VENDORED: bool = {};\n\
+         /// The cargo features newt-sys was built with.\n\
+         pub const FEATURES: &[&str] = &{:?};\n\
+         /// The target newt-sys was built for.\n\
+         pub const TARGET: &str = {:?};\n",
+        statik, vendored, features(), build_config::rust_target());
+
+    fs::write(out_dir.join("build_info.rs"), module)
+}
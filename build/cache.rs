@@ -0,0 +1,112 @@
+use build_config::env_var;
+
+use sha2::{Digest,Sha256};
+
+use std::fs::{self,OpenOptions};
+use std::io::{self,Write};
+use std::path::{Path,PathBuf};
+use std::process::{self,Command,Stdio};
+use std::thread;
+use std::time::Duration;
+
+// Stands in for OUT_DIR in build stamps, so that they still match after
+// OUT_DIR has moved and cached ones are shared by every target directory
+// using the cache.
+pub const OUT_DIR: &str = "@OUT_DIR@";
+
+// Stands in for the installation prefix in a build's key and stamp.
+pub const PREFIX: &str = "@PREFIX@";
+
+// A directory to install the bundled libraries into instead of OUT_DIR,
+// shared by every build using the same NEWT_SYS_CACHE_DIR.
+pub fn dir() -> Option<PathBuf> {
+    env_var("NEWT_SYS_CACHE_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+// Identifies a build by everything that goes into it: versions, target,
+// compiler, flags and configure options.
+pub fn key(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts.iter() {
+        hasher.update(part.as_bytes());
+        hasher.update(b"\n");
+    }
+    let digest = format!("{:x}", hasher.finalize());
+    digest[..16].to_string()
+}
+
+// An exclusive lock on one entry of the cache, released when dropped.
+pub struct Lock {
+    path: PathBuf
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// How long the lock file `path` has existed.
+fn age(path: &Path) -> Option<Duration> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+        .and_then(|modified| modified.elapsed().ok())
+}
+
+// Whether the build that created the lock file `path` was killed before
+// removing it. The file holds the build's process id, which is looked for
+// with kill -0 on Unix. Elsewhere, and if the build didn't get to writing
+// its id, the lock is given up on once it is older than any build takes.
+fn is_stale(path: &Path) -> bool {
+    let pid = fs::read_to_string(path).unwrap_or_default();
+    let pid = pid.trim();
+    if cfg!(unix) && !pid.is_empty() {
+        return Command::new("kill")
+            .args(["-0", pid])
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| !status.success())
+            .unwrap_or(false);
+    }
+
+    let limit = if pid.is_empty() { 60 } else { 24 * 60 * 60 };
+    age(path).is_some_and(|age| age > Duration::from_secs(limit))
+}
+
+// Wait until no other build is using the entry `name` in `dir`. The lock
+// is a file only one build can create.
+pub fn lock(dir: &Path, name: &str) -> Result<Lock, String> {
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("unable to create {}: {}", dir.display(), e))?;
+
+    let path = dir.join(format!("{}.lock", name));
+    let mut waiting = false;
+    loop {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                let lock = Lock { path };
+                write!(file, "{}", process::id()).map_err(|e| {
+                    format!("unable to write {}: {}", lock.path.display(), e)
+                })?;
+                return Ok(lock);
+            },
+            Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                if is_stale(&path) {
+                    let _ = fs::remove_file(&path);
+                    continue;
+                }
+                if !waiting {
+                    println!("cargo:warning=waiting for another build to \
+                              remove {}", path.display());
+                    waiting = true;
+                }
+                thread::sleep(Duration::from_millis(500));
+            },
+            Err(e) => {
+                return Err(format!("unable to create {}: {}",
+                                   path.display(), e));
+            }
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use build_config;
+use reproducible;
+
+use std::env;
+use std::fs;
+use std::path::{Path,PathBuf};
+use std::process::Command;
+
+// Objects in newt's libnewt.a, see LIBOBJS in its Makefile.in.
+const NEWT_SOURCES: &[&str] = &[
+    "newt.c", "button.c", "form.c", "checkbox.c", "entry.c", "label.c",
+    "listbox.c", "scrollbar.c", "textbox.c", "scale.c", "grid.c",
+    "windows.c", "buttonbar.c", "checkboxtree.c"
+];
+
+// What slang's configure finds on any POSIX system.
+const SLANG_POSIX: &[&str] = &[
+    "HAVE_LONG_LONG", "HAVE_STDLIB_H", "HAVE_UNISTD_H", "HAVE_TERMIOS_H",
+    "HAVE_MEMORY_H", "HAVE_MEMSET", "HAVE_MEMCPY", "HAVE_SETLOCALE",
+    "HAVE_LOCALECONV", "HAVE_LOCALE_H", "HAVE_LANGINFO_H",
+    "HAVE_NL_LANGINFO_CODESET", "HAVE_FENV_H", "HAVE_FECLEAREXCEPT",
+    "HAVE_VFSCANF", "HAVE_STRTOD", "HAVE_ATOLL", "HAVE_STRTOLL",
+    "HAVE_FCNTL_H", "HAVE_VSNPRINTF", "HAVE_SNPRINTF", "HAVE_SYS_FCNTL_H",
+    "HAVE_SYS_STATVFS_H", "HAVE_SYS_TYPES_H", "HAVE_SYS_WAIT_H",
+    "HAVE_SYS_TIMES_H", "HAVE_SYS_TIME_H", "HAVE_UTIME_H", "HAVE_UTIME",
+    "HAVE_UTIMES", "HAVE_GETITIMER", "HAVE_SETITIMER", "HAVE_SYS_MMAN_H",
+    "HAVE_SYS_RESOURCE_H", "HAVE_GETRUSAGE", "HAVE_ATEXIT", "HAVE_PUTENV",
+    "HAVE_GETCWD", "HAVE_TCGETATTR", "HAVE_TCSETATTR", "HAVE_CFGETOSPEED",
+    "HAVE_LSTAT", "HAVE_KILL", "HAVE_KILLPG", "HAVE_CHOWN", "HAVE_LCHOWN",
+    "HAVE_POPEN", "HAVE_UMASK", "HAVE_READLINK", "HAVE_SYMLINK", "HAVE_LINK",
+    "HAVE_TIMES", "HAVE_GMTIME", "HAVE_GMTIME_R", "HAVE_LOCALTIME_R",
+    "HAVE_CTIME_R", "HAVE_GETTIMEOFDAY", "HAVE_MKTIME", "HAVE_MKFIFO",
+    "HAVE_MMAP", "HAVE_TTYNAME_R", "HAVE_TTYNAME", "HAVE_STATVFS",
+    "HAVE_GETPPID", "HAVE_GETGID", "HAVE_GETEGID", "HAVE_GETEUID",
+    "HAVE_GETUID", "HAVE_SETGID", "HAVE_SETUID", "HAVE_GETPGID",
+    "HAVE_GETPGRP", "HAVE_SETPGID", "HAVE_GETSID", "HAVE_SETSID",
+    "HAVE_GETPRIORITY", "HAVE_SETPRIORITY", "HAVE_ACOSH", "HAVE_ASINH",
+    "HAVE_ATANH", "HAVE_ISINF", "HAVE_ISNAN", "HAVE_HYPOT", "HAVE_ATAN2",
+    "HAVE_FREXP", "HAVE_FREXPF", "HAVE_LDEXP", "HAVE_LDEXPF",
+    "HAVE_DIRENT_H", "HAVE_FSEEKO", "HAVE_SETVBUF", "HAVE_DLFCN_H",
+    "HAVE_SYS_UTSNAME_H", "HAVE_UNAME", "HAVE_FLOCK", "HAVE_ALARM",
+    "HAVE_PAUSE", "HAVE_SOCKET", "HAVE_SOCKETPAIR", "HAVE_SYS_SOCKET_H",
+    "HAVE_NETINET_IN_H", "HAVE_ARPA_INET_H", "HAVE_SYS_UN_H",
+    "HAVE_CONFSTR", "HAVE_SYSCONF", "HAVE_PATHCONF", "HAVE_ENVIRON",
+    "HAVE_SIGLONGJMP", "HAVE_SIGACTION", "HAVE_SIGPROCMASK",
+    "HAVE_SIGEMPTYSET", "HAVE_SIGADDSET", "HAVE_FORK", "HAVE_WAITPID"
+];
+
+// glibc and musl extensions.
+const SLANG_LINUX: &[&str] = &["HAVE_MALLOC_H", "HAVE_SINCOS", "HAVE_SINCOSF"];
+
+fn is_linux() -> bool {
+    build_config::rust_target().contains("-linux")
+}
+
+fn pointer_width() -> u32 {
+    env::var("CARGO_CFG_TARGET_POINTER_WIDTH").ok()
+        .and_then(|width| width.parse().ok())
+        .unwrap_or(64)
+}
+
+// `long` is 32 bits on 64-bit Windows.
+fn sizeof_long() -> u32 {
+    if build_config::rust_target().contains("-windows") {
+        4
+    } else {
+        pointer_width() / 8
+    }
+}
+
+fn create_dir(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("unable to create {}: {}", dir.display(), e))
+}
+
+fn copy(from: &Path, to: &Path) -> Result<(), String> {
+    fs::copy(from, to).map(|_| ()).map_err(|e| {
+        format!("unable to copy {} to {}: {}", from.display(), to.display(), e)
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents)
+        .map_err(|e| format!("unable to write {}: {}", path.display(), e))
+}
+
+// `value` as a C string literal, for paths in -D flags.
+fn c_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// A cc::Build for the target using the archiver the bundled libraries are
+// built with, and `flags`.
+pub fn target_build(flags: &[String]) -> cc::Build {
+    let mut build = cc::Build::new();
+    // The cc crate reads AR itself, arguments included.
+    if build_config::target_var("AR").is_none() {
+        build.archiver(build_config::archiver());
+    }
+    for flag in flags.iter() {
+        build.flag(flag);
+    }
+    build
+}
+
+fn new_build(src_dir: &Path, flags: &[String]) -> cc::Build {
+    let mut build = target_build(flags);
+    build.out_dir(src_dir.join("cc-build"))
+        .cargo_metadata(false)
+        .cargo_warnings(false)
+        .warnings(false);
+    for var in build_config::temp_vars() {
+        build.env(var, build_config::temp_dir());
+    }
+    build
+}
+
+// The command `build` compiles with, with NEWT_SYS_CC_WRAPPER in front of
+// the compiler in place of a launcher the cc crate found, the way
+// build_config::cc_command() puts it in CC for configure. The arguments
+// given with the compiler in CC stay right after it.
+fn wrapped_compiler(build: &cc::Build, wrapper: &str)
+    -> Result<Command, String> {
+    let tool = build.try_get_compiler().map_err(|e| e.to_string())?;
+    let compiler = tool.to_command();
+    let mut args = compiler.get_args();
+    // A launcher runs the compiler, which is then its first argument.
+    if compiler.get_program() != tool.path() {
+        args.next();
+    }
+
+    let mut cmd = Command::new(wrapper);
+    cmd.arg(tool.path()).args(args);
+    for (name, value) in compiler.get_envs() {
+        if let Some(value) = value {
+            cmd.env(name, value);
+        }
+    }
+    Ok(cmd)
+}
+
+// Compile the files of `build` into lib`name`.a in `out_dir`, which has to
+// be the one `build` was given. The cc crate only takes a launcher from the
+// CC variables themselves, so with NEWT_SYS_CC_WRAPPER the objects are
+// compiled here and only archived by it. Nothing is emitted for cargo.
+pub fn compile(build: &cc::Build, name: &str, out_dir: &Path)
+    -> Result<(), String> {
+    let wrapper = match build_config::cc_wrapper() {
+        Some(wrapper) => wrapper,
+        None => return build.try_compile(name).map_err(|e| e.to_string())
+    };
+
+    let mut objects = Vec::new();
+    for (i, file) in build.get_files().enumerate() {
+        let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+        let object = out_dir.join(format!("{}-{}.o", i, stem));
+        let mut cmd = wrapped_compiler(build, &wrapper)?;
+        cmd.arg("-c").arg(file).arg("-o").arg(&object);
+        let output = cmd.output()
+            .map_err(|e| format!("unable to execute {:?}: {}", cmd, e))?;
+        if !output.status.success() {
+            return Err(format!("{:?} failed ({}): {}", cmd, output.status,
+                               String::from_utf8_lossy(&output.stderr)));
+        }
+        objects.push(object);
+    }
+    build.try_create_archive(name, &objects).map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+// Compile `build` into lib`name`.a and install it into `{prefix}/lib`.
+fn install_lib(build: &cc::Build, name: &str, src_dir: &Path, prefix: &Path)
+    -> Result<(), String> {
+    compile(build, name, &src_dir.join("cc-build"))?;
+
+    let lib_dir = prefix.join("lib");
+    create_dir(&lib_dir)?;
+    let archive = format!("lib{}.a", name);
+    let installed = lib_dir.join(&archive);
+    copy(&src_dir.join("cc-build").join(&archive), &installed)?;
+    if !build_config::is_darwin() {
+        reproducible::normalize_archive(&installed)?;
+    }
+    Ok(())
+}
+
+fn install_headers(dir: &Path, headers: &[&str], prefix: &Path)
+    -> Result<(), String> {
+    let include_dir = prefix.join("include");
+    create_dir(&include_dir)?;
+    for header in headers.iter() {
+        copy(&dir.join(header), &include_dir.join(header))?;
+    }
+    Ok(())
+}
+
+// Fill in src/config.hin the way config.status would.
+fn slang_sysconf(template: &str) -> String {
+    let width = pointer_width() / 8;
+    let mut values: Vec<(&str, String)> = vec![
+        ("SIZEOF_SHORT", String::from("2")),
+        ("SIZEOF_INT", String::from("4")),
+        ("SIZEOF_LONG", sizeof_long().to_string()),
+        ("SIZEOF_FLOAT", String::from("4")),
+        ("SIZEOF_DOUBLE", String::from("8")),
+        ("SIZEOF_LONG_LONG", String::from("8")),
+        ("SIZEOF_OFF_T", String::from("8")),
+        ("SIZEOF_SIZE_T", width.to_string())
+    ];
+    if width < 8 {
+        values.push(("_FILE_OFFSET_BITS", String::from("64")));
+    }
+
+    let mut defines: Vec<&str> = SLANG_POSIX.to_vec();
+    if is_linux() {
+        defines.extend(SLANG_LINUX.iter());
+    }
+    values.extend(defines.into_iter().map(|name| (name, String::from("1"))));
+
+    let mut sysconf = String::new();
+    for line in template.lines() {
+        let name = line.strip_prefix("#undef ").map(str::trim);
+        match name.and_then(|name| values.iter().find(|v| v.0 == name)) {
+            Some(&(name, ref value)) =>
+                sysconf.push_str(&format!("#define {} {}", name, value)),
+            None if name.is_some() =>
+                sysconf.push_str(&format!("/* {} */", line)),
+            None => sysconf.push_str(line)
+        }
+        sysconf.push('\n');
+    }
+    sysconf
+}
+
+// System libraries the static libslang needs.
+pub fn slang_libs() -> Vec<String> {
+    let mut libs = vec![String::from("m")];
+    if is_linux() {
+        libs.push(String::from("dl"));
+    }
+    libs
+}
+
+// Build libslang.a from the slang tree at `src_dir` and install it with its
+// headers into `prefix`.
+pub fn slang(src_dir: &Path, prefix: &Path, flags: &[String])
+    -> Result<(), String> {
+    let src = src_dir.join("src");
+    let template = fs::read_to_string(src.join("config.hin")).map_err(|e| {
+        format!("unable to read {}: {}", src.join("config.hin").display(), e)
+    })?;
+    // The Makefile copies sysconf.h to the config.h the sources include.
+    let sysconf = slang_sysconf(&template);
+    write(&src.join("sysconf.h"), &sysconf)?;
+    write(&src.join("config.h"), &sysconf)?;
+    copy(&src.join("terminfo/default.inc"), &src.join("terminfo.inc"))?;
+
+    let modules = fs::read_to_string(src.join("modules.unx")).map_err(|e| {
+        format!("unable to read {}: {}", src.join("modules.unx").display(), e)
+    })?;
+
+    let prefix_str = prefix.display().to_string();
+    let mut build = new_build(src_dir, flags);
+    build.include(&src)
+        .define("unix", None)
+        .define("SLANG", None)
+        .define("SLANG_DOC_DIR",
+                c_string(&format!("{}/share/doc/slang/v2", prefix_str))
+                    .as_str())
+        .define("SLANG_INSTALL_PREFIX", c_string(&prefix_str).as_str())
+        .define("MODULE_INSTALL_DIR",
+                c_string(&format!("{}/lib/slang/v2/modules", prefix_str))
+                    .as_str())
+        .define("MISC_TERMINFO_DIRS", build_config::misc_terminfo_dirs()
+                .unwrap_or_else(|| String::from("\"/etc/terminfo\""))
+                .as_str());
+    for module in modules.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_whitespace().next()) {
+        build.file(src.join(format!("{}.c", module)));
+    }
+
+    install_lib(&build, "slang", src_dir, prefix)?;
+    install_headers(&src, &["slang.h", "slcurses.h"], prefix)
+}
+
+fn newt_config(version: &str) -> String {
+    let mut defines = vec![
+        (String::from("PACKAGE"), String::from("\"newt\"")),
+        (String::from("VERSION"), format!("\"{}\"", version)),
+        (String::from("SIZEOF_LONG"), sizeof_long().to_string()),
+        (String::from("SIZEOF_LONG_LONG"), String::from("8")),
+        (String::from("SIZEOF_VOID_P"), (pointer_width() / 8).to_string()),
+        (String::from("HAVE_SYS_SELECT_H"), String::from("1"))
+    ];
+
+    // The BSDs declare alloca() in stdlib.h.
+    if is_linux() || build_config::is_darwin() {
+        defines.push((String::from("HAVE_ALLOCA_H"), String::from("1")));
+    }
+
+    // newt talks to the GPM daemon itself, there's no libgpm to link.
+    if cfg!(feature = "gpm") {
+        defines.push((String::from("USE_GPM"), String::from("1")));
+    }
+
+    let mut config = String::from("/* Generated by newt-sys */\n");
+    for (name, value) in defines.iter() {
+        config.push_str(&format!("#define {} {}\n", name, value));
+    }
+    config
+}
+
+// Build libnewt.a from the newt tree at `src_dir`, using the slang headers
+// in `include_paths`, and install it with newt.h into `prefix`.
+pub fn newt(src_dir: &Path, prefix: &Path, version: &str,
+            include_paths: &[PathBuf], flags: &[String])
+    -> Result<(), String> {
+    write(&src_dir.join("config.h"), &newt_config(version))?;
+
+    let mut build = new_build(src_dir, flags);
+    build.include(src_dir)
+        .includes(include_paths)
+        .define("_GNU_SOURCE", None);
+    for source in NEWT_SOURCES.iter() {
+        build.file(src_dir.join(source));
+    }
+
+    install_lib(&build, "newt", src_dir, prefix)?;
+    install_headers(src_dir, &["newt.h"], prefix)
+}
@@ -0,0 +1,25 @@
+use sha2::{Digest,Sha256};
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+pub fn sha256(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+pub fn verify(path: &Path, expected: &str) -> Result<(), String> {
+    let digest = sha256(path)
+        .map_err(|e| format!("unable to read {}: {}", path.display(), e))?;
+
+    if digest != expected {
+        return Err(format!("SHA-256 checksum mismatch for {}\n  \
+                            expected: {}\n  \
+                            found:    {}", path.display(), expected,
+                           digest));
+    }
+    Ok(())
+}
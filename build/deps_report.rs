@@ -0,0 +1,183 @@
+use build_config;
+use lazy_static::lazy_static;
+use library::Library;
+
+use std::fs;
+use std::io;
+use std::path::{Path,PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+const REPORT_FILE: &str = "newt-sys-deps.json";
+
+// The arguments and environment configure was run with.
+struct Configure {
+    args: Vec<String>,
+    env: Vec<(String, String)>
+}
+
+// How a bundled library was produced: "configure", "cc" or "prebuilt".
+struct Build {
+    package: String,
+    builder: &'static str,
+    configure: Option<Configure>
+}
+
+struct Entry {
+    name: String,
+    link: &'static str,
+    version: Option<String>,
+    root: Option<PathBuf>,
+    include_paths: Vec<PathBuf>,
+    link_paths: Vec<PathBuf>,
+    libs: Vec<String>
+}
+
+lazy_static! {
+    static ref BUILDS: Mutex<Vec<Build>> = Mutex::new(Vec::new());
+    static ref ENTRIES: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+}
+
+// The licenses of the bundled libraries, as SPDX identifiers.
+fn license(package: &str) -> Option<&'static str> {
+    match package {
+        "newt" => Some("LGPL-2.0-only"),
+        "popt" => Some("MIT"),
+        "slang" => Some("GPL-2.0-or-later"),
+        _ => None
+    }
+}
+
+// Note that `package` was built or installed by `builder`, running
+// `configure` if given.
+pub fn built(package: &str, builder: &'static str,
+             configure: Option<&Command>) {
+    let configure = configure.map(|cmd| Configure {
+        args: cmd.get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect(),
+        env: cmd.get_envs()
+            .filter_map(|(name, value)| {
+                Some((name.to_string_lossy().into_owned(),
+                      value?.to_string_lossy().into_owned()))
+            })
+            .collect()
+    });
+    BUILDS.lock().unwrap().push(Build {
+        package: package.to_string(),
+        builder,
+        configure
+    });
+}
+
+// Note that `library` is linked for `name`, "static" or "dynamic".
+pub fn record(name: &str, library: &Library, link: &'static str) {
+    ENTRIES.lock().unwrap().push(Entry {
+        name: name.to_string(),
+        link,
+        version: library.version.clone(),
+        root: library.root.clone(),
+        include_paths: library.include_paths.clone(),
+        link_paths: library.link_paths.clone(),
+        libs: library.libs.clone()
+    });
+}
+
+fn string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}",
+                                                             c as u32)),
+            c => json.push(c)
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn optional(value: Option<&str>) -> String {
+    value.map_or(String::from("null"), string)
+}
+
+fn list<T: AsRef<str>>(values: &[T]) -> String {
+    let values: Vec<String> = values.iter()
+        .map(|value| string(value.as_ref()))
+        .collect();
+    format!("[{}]", values.join(", "))
+}
+
+fn paths(paths: &[PathBuf]) -> String {
+    let paths: Vec<String> = paths.iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    list(&paths)
+}
+
+fn entry(entry: &Entry, build: Option<&Build>) -> String {
+    let source = match build {
+        Some(build) if build.builder == "prebuilt" => "prebuilt",
+        Some(_) => "vendored",
+        None => "system"
+    };
+    let configure = match build.and_then(|build| build.configure.as_ref()) {
+        Some(configure) => {
+            let env: Vec<String> = configure.env.iter()
+                .map(|(name, value)| {
+                    format!("{}: {}", string(name), string(value))
+                })
+                .collect();
+            format!("{{\"args\": {}, \"env\": {{{}}}}}",
+                    list(&configure.args), env.join(", "))
+        },
+        None => String::from("null")
+    };
+    let license = if build.is_some() { license(&entry.name) } else { None };
+
+    format!("    {{\n      \
+             \"name\": {},\n      \
+             \"source\": {},\n      \
+             \"builder\": {},\n      \
+             \"version\": {},\n      \
+             \"license\": {},\n      \
+             \"link\": {},\n      \
+             \"root\": {},\n      \
+             \"include_paths\": {},\n      \
+             \"link_paths\": {},\n      \
+             \"libs\": {},\n      \
+             \"configure\": {}\n    }}",
+            string(&entry.name), string(source),
+            optional(build.map(|build| build.builder)),
+            optional(entry.version.as_deref()), optional(license),
+            string(entry.link),
+            optional(entry.root.as_ref()
+                         .map(|root| root.display().to_string())
+                         .as_deref()),
+            paths(&entry.include_paths), paths(&entry.link_paths),
+            list(&entry.libs), configure)
+}
+
+// Write `{out_dir}/newt-sys-deps.json`, describing the recorded libraries
+// for packaging and license tooling.
+pub fn write(out_dir: &Path, statik: bool, vendored: bool) -> io::Result<()> {
+    let builds = BUILDS.lock().unwrap();
+    let entries: Vec<String> = ENTRIES.lock().unwrap().iter()
+        .map(|e| entry(e, builds.iter().find(|b| b.package == e.name)))
+        .collect();
+
+    let report = format!("{{\n  \
+                          \"target\": {},\n  \
+                          \"static\": {},\n  \
+                          \"vendored\": {},\n  \
+                          \"libraries\": [\n{}\n  ]\n}}\n",
+                         string(&build_config::rust_target()), statik,
+                         vendored, entries.join(",\n"));
+    let path = out_dir.join(REPORT_FILE);
+    fs::write(&path, report)?;
+    println!("cargo:deps_report={}", path.display());
+    Ok(())
+}
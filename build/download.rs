@@ -0,0 +1,256 @@
+use build_config::{self,env_var};
+use checksum;
+use error::{BuildError,Phase};
+
+use std::env;
+use std::fs;
+use std::path::{Path,PathBuf};
+use std::process;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+#[cfg(feature = "download-sources")]
+const NEWT_URLS: &[&str] = &[
+    "https://releases.pagure.org/newt",
+    "https://fedorahosted.org/releases/n/e/newt"
+];
+// popt's GitHub repository has no release archives, only snapshots of its
+// tags without the generated configure script.
+#[cfg(feature = "download-sources")]
+const POPT_URLS: &[&str] = &[
+    "https://ftp.rpm.org/popt/releases/popt-1.x"
+];
+#[cfg(feature = "download-sources")]
+const SLANG_URLS: &[&str] = &[
+    "https://www.jedsoft.org/releases/slang",
+    "https://www.jedsoft.org/releases/slang/old"
+];
+
+// Attempts per mirror, waiting 1, 2, 4, ... seconds between them.
+const ATTEMPTS: u32 = 3;
+
+#[cfg(feature = "download-sources")]
+fn base_urls(package: &str) -> &'static [&'static str] {
+    match package {
+        "newt" => NEWT_URLS,
+        "popt" => POPT_URLS,
+        "slang" => SLANG_URLS,
+        _ => panic!("Unexpected package requested to be downloaded: {}", package)
+    }
+}
+
+// NEWT_SYS_MIRROR, a directory with all of the archives, comes first.
+#[cfg(feature = "download-sources")]
+fn urls(package: &str, archive: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    if let Some(mirror) = env_var("NEWT_SYS_MIRROR") {
+        urls.push(format!("{}/{}", mirror.trim_end_matches('/'), archive));
+    }
+    urls.extend(base_urls(package).iter()
+        .map(|base| format!("{}/{}", base, archive)));
+    urls
+}
+
+// The per-user directory downloaded archives are kept in, so that fresh
+// target directories and `cargo clean` don't download them again:
+// NEWT_SYS_DOWNLOAD_CACHE, where an empty value turns it off, or newt-sys in
+// the user's cache directory.
+fn user_cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env_var("NEWT_SYS_DOWNLOAD_CACHE") {
+        return if dir.is_empty() { None } else { Some(PathBuf::from(dir)) };
+    }
+
+    let base = if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CACHE_HOME")
+            .filter(|dir| Path::new(dir).is_absolute())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME").map(|home| Path::new(&home).join(".cache"))
+            })
+    };
+    base.map(|base| base.join("newt-sys"))
+}
+
+// Where `archive` with the digest `sha256` is kept in the user's cache.
+fn cached_path(archive: &str, sha256: &str) -> Option<PathBuf> {
+    user_cache_dir().map(|dir| dir.join(sha256).join(archive))
+}
+
+// `path` with `.{ext}` appended, for a file written before it is renamed
+// into place.
+fn part_path(path: &Path, ext: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(ext);
+    PathBuf::from(name)
+}
+
+// Copy `archive` from the user's cache to `dest`, if it is there with the
+// right digest.
+fn from_user_cache(archive: &str, sha256: &str, dest: &Path) -> bool {
+    let cached = match cached_path(archive, sha256) {
+        Some(ref path) if path.is_file() => path.clone(),
+        _ => return false
+    };
+    if checksum::verify(&cached, sha256).is_err() {
+        let _ = fs::remove_file(&cached);
+        return false;
+    }
+
+    let partial = part_path(dest, "part");
+    fs::copy(&cached, &partial)
+        .and_then(|_| fs::rename(&partial, dest))
+        .is_ok()
+}
+
+// Keep the downloaded `dest` in the user's cache. Failing to is only worth
+// a warning.
+fn to_user_cache(archive: &str, sha256: &str, dest: &Path) {
+    let cached = match cached_path(archive, sha256) {
+        Some(path) => path,
+        None => return
+    };
+    // Written under a name of its own first, as other builds may be
+    // reading the cache at the same time.
+    let partial = part_path(&cached, &format!("{}.part", process::id()));
+    let result = cached.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::copy(dest, &partial))
+        .and_then(|_| fs::rename(&partial, &cached));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&partial);
+        println!("cargo:warning=unable to cache {} in {}: {}", archive,
+                 cached.display(), e);
+    }
+}
+
+enum Failure {
+    // Worth another attempt at the same URL.
+    Transient(BuildError),
+    // The URL doesn't have the archive, try the next one.
+    Missing(BuildError)
+}
+
+// Fetch `url` into `partial`, continuing where an earlier attempt stopped.
+fn fetch(package: &str, url: &str, partial: &Path) -> Result<(), Failure> {
+    let mut cmd = Command::new("curl");
+    cmd.args(["--fail", "--location", "--silent", "--show-error"])
+        .args(["--continue-at", "-"])
+        .args(["--write-out", "%{http_code}"])
+        .arg("--output")
+        .arg(partial)
+        .arg(url);
+
+    let output = cmd.output().map_err(|e| {
+        Failure::Missing(BuildError::new(package, Phase::Download,
+                             format!("unable to execute curl: {}", e))
+            .command(format!("{:?}", cmd)))
+    })?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let error = BuildError::new(package, Phase::Download,
+                                format!("unable to download {}", url))
+        .command(format!("{:?}", cmd))
+        .status(output.status)
+        .stderr(&output.stderr);
+
+    // The server can't continue the partial file (curl's exit status 33),
+    // or it doesn't fit the archive. Start over.
+    let code: u32 = String::from_utf8_lossy(&output.stdout).trim()
+        .parse().unwrap_or(0);
+    if output.status.code() == Some(33) || code == 416 {
+        let _ = fs::remove_file(partial);
+        return Err(Failure::Transient(error));
+    }
+
+    match code {
+        408 | 429 => Err(Failure::Transient(error)),
+        400..=499 => Err(Failure::Missing(error)),
+        _ => Err(Failure::Transient(error))
+    }
+}
+
+#[cfg(feature = "download-sources")]
+pub fn download(package: &str, archive: &str, sha256: &str,
+                download_dir: &Path) -> Result<PathBuf, BuildError> {
+    download_from(package, &urls(package, archive), archive, sha256,
+                  download_dir)
+}
+
+// Fetch `archive` from the first of `urls` that has it into
+// `download_dir`, unless it is already there or in the user's cache.
+pub fn download_from(package: &str, urls: &[String], archive: &str,
+                     sha256: &str, download_dir: &Path)
+    -> Result<PathBuf, BuildError> {
+    let dest = download_dir.join(archive);
+    if dest.exists() {
+        return Ok(dest);
+    }
+
+    fs::create_dir_all(download_dir).map_err(|e| {
+        BuildError::new(package, Phase::Download,
+                        format!("unable to create {}: {}",
+                                download_dir.display(), e))
+    })?;
+    if from_user_cache(archive, sha256, &dest) {
+        return Ok(dest);
+    }
+
+    // Offline, only local mirrors can be used.
+    let urls: Vec<&String> = urls.iter()
+        .filter(|url| !build_config::offline() || url.starts_with("file:"))
+        .collect();
+    if urls.is_empty() {
+        return Err(BuildError::new(package, Phase::Download, format!(
+            "cargo is offline (CARGO_NET_OFFLINE) and {} isn't in {}. Copy \
+             it there, or serve it from a file:// URL", archive,
+            download_dir.display())));
+    }
+
+    let partial = part_path(&dest, "part");
+    let mut last_error = None;
+    for url in urls.iter() {
+        for attempt in 0..ATTEMPTS {
+            if attempt > 0 {
+                thread::sleep(Duration::from_secs(1 << (attempt - 1)));
+            }
+
+            match fetch(package, url, &partial) {
+                Ok(()) => (),
+                Err(Failure::Transient(e)) => {
+                    last_error = Some(e);
+                    continue;
+                },
+                Err(Failure::Missing(e)) => {
+                    last_error = Some(e);
+                    break;
+                }
+            }
+
+            if let Err(e) = checksum::verify(&partial, sha256) {
+                let _ = fs::remove_file(&partial);
+                last_error = Some(BuildError::new(package, Phase::Verify, e));
+                break;
+            }
+
+            fs::rename(&partial, &dest).map_err(|e| {
+                BuildError::new(package, Phase::Download,
+                    format!("unable to move {} into place: {}",
+                            dest.display(), e))
+            })?;
+            to_user_cache(archive, sha256, &dest);
+            return Ok(dest);
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| {
+        BuildError::new(package, Phase::Download,
+                        format!("no download location for {}", archive))
+    }))
+}
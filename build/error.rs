@@ -0,0 +1,196 @@
+use std::fmt;
+use std::path::Path;
+use std::process::ExitStatus;
+
+const STDERR_TAIL_LINES: usize = 20;
+
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Phase {
+    Prepare,
+    #[cfg(any(feature = "download-sources", feature = "bundle-cache"))]
+    Download,
+    Verify,
+    Extract,
+    Patch,
+    Configure,
+    Make,
+    Probe,
+    #[cfg(feature = "buildtime-bindgen")]
+    Bindgen
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Phase::Prepare => "prepare",
+            #[cfg(any(feature = "download-sources", feature = "bundle-cache"))]
+            Phase::Download => "download",
+            Phase::Verify => "verify",
+            Phase::Extract => "extract",
+            Phase::Patch => "patch",
+            Phase::Configure => "configure",
+            Phase::Make => "make",
+            Phase::Probe => "pkg-config",
+            #[cfg(feature = "buildtime-bindgen")]
+            Phase::Bindgen => "bindgen"
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug)]
+pub struct BuildError {
+    package: String,
+    phase: Phase,
+    message: String,
+    command: Option<Box<str>>,
+    status: Option<ExitStatus>,
+    stderr: Option<String>,
+    log: Option<Box<Path>>
+}
+
+impl BuildError {
+    pub fn new<S: Into<String>>(package: &str, phase: Phase, message: S)
+        -> BuildError {
+        BuildError {
+            package: package.to_string(),
+            phase,
+            message: message.into(),
+            command: None,
+            status: None,
+            stderr: None,
+            log: None
+        }
+    }
+
+    pub fn command<S: Into<String>>(mut self, command: S) -> BuildError {
+        self.command = Some(command.into().into_boxed_str());
+        self
+    }
+
+    pub fn status(mut self, status: ExitStatus) -> BuildError {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn stderr(mut self, stderr: &[u8]) -> BuildError {
+        let stderr = String::from_utf8_lossy(stderr);
+        let lines: Vec<&str> = stderr.lines().collect();
+        let start = lines.len().saturating_sub(STDERR_TAIL_LINES);
+        self.stderr = Some(lines[start..].join("\n"));
+        self
+    }
+
+    pub fn log(mut self, log: Option<&Path>) -> BuildError {
+        self.log = log.map(Box::from);
+        self
+    }
+
+    #[cfg(feature = "bundle-cache")]
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    // configure failed because config.guess or config.sub don't know the
+    // build or host system.
+    pub fn is_unknown_host(&self) -> bool {
+        let stderr = self.stderr.as_deref().unwrap_or("");
+        self.phase == Phase::Configure &&
+            (stderr.contains("cannot guess build type") ||
+             stderr.contains("Invalid configuration") ||
+             stderr.contains("not recognized"))
+    }
+
+    fn hint(&self) -> Option<&'static str> {
+        let stderr = self.stderr.as_deref().unwrap_or("");
+
+        if stderr.contains("no acceptable C compiler") ||
+           stderr.contains("C compiler cannot create executables") {
+            return Some("Install a working C compiler (e.g. gcc or clang) \
+                         or point the CC environment variable at one.");
+        }
+
+        if stderr.contains("No such file or directory") &&
+           stderr.contains(".h") {
+            return Some("A required C header is missing. Install the \
+                         development package that provides it.");
+        }
+
+        if self.is_unknown_host() {
+            return Some("The config.guess and config.sub scripts don't know \
+                         this system. Install autoconf and automake to have \
+                         them regenerated, or point NEWT_SYS_GNUCONFIG_DIR \
+                         at newer copies.");
+        }
+
+        match self.phase {
+            Phase::Prepare if self.message.contains("missing prerequisites") =>
+                Some("Install the programs listed above, or point CC or \
+                      PKG_CONFIG at the ones to use."),
+            Phase::Prepare if self.message.contains("GNU Make") =>
+                Some("Install GNU Make (available as `gmake` on BSD \
+                      systems) and make sure it is in PATH."),
+            #[cfg(any(feature = "download-sources", feature = "bundle-cache"))]
+            Phase::Download if self.message.contains("CARGO_NET_OFFLINE") =>
+                Some("Build once while online to fetch the archives, point \
+                      NEWT_SYS_MIRROR at a file:// directory with them, or \
+                      disable the `download-sources` feature to build from \
+                      the bundled archives."),
+            #[cfg(feature = "download-sources")]
+            Phase::Download =>
+                Some("Check your network connection, or disable the \
+                      `download-sources` feature to build from the \
+                      bundled archives."),
+            Phase::Verify =>
+                Some("The source archive is corrupt or has been tampered \
+                      with. Remove it and fetch a fresh copy."),
+            Phase::Patch =>
+                Some("A patch doesn't apply to this version of the sources. \
+                      Update or remove it, and make sure `patch` is \
+                      installed."),
+            Phase::Probe =>
+                Some("Make sure pkg-config is installed and in PATH and \
+                      that the libnewt development package (e.g. \
+                      libnewt-dev or newt-devel) is installed."),
+            #[cfg(feature = "buildtime-bindgen")]
+            Phase::Bindgen =>
+                Some("bindgen needs libclang. Install it (e.g. libclang-dev \
+                      or clang-devel) or point LIBCLANG_PATH at it."),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "failed to build {} ({} phase): {}",
+                 self.package, self.phase, self.message)?;
+
+        if let Some(ref command) = self.command {
+            writeln!(f, "  command: {}", command)?;
+        }
+
+        if let Some(ref status) = self.status {
+            writeln!(f, "  status:  {}", status)?;
+        }
+
+        if let Some(ref log) = self.log {
+            writeln!(f, "  log:     {}", log.display())?;
+        }
+
+        if let Some(ref stderr) = self.stderr {
+            if !stderr.is_empty() {
+                writeln!(f, "  stderr (last {} lines):", STDERR_TAIL_LINES)?;
+                for line in stderr.lines() {
+                    writeln!(f, "    {}", line)?;
+                }
+            }
+        }
+
+        if let Some(hint) = self.hint() {
+            writeln!(f)?;
+            writeln!(f, "hint: {}", hint)?;
+        }
+        Ok(())
+    }
+}
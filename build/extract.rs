@@ -0,0 +1,171 @@
+use bzip2::read::BzDecoder;
+use flate2::read::GzDecoder;
+use tar::{Archive,EntryType};
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component,Path};
+
+fn decoder(archive: &Path, file: File) -> Result<Box<dyn Read>, String> {
+    let name = archive.to_string_lossy();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Ok(Box::new(GzDecoder::new(file)))
+    } else if name.ends_with(".tar.bz2") {
+        Ok(Box::new(BzDecoder::new(file)))
+    } else {
+        Err(format!("unsupported archive format: {}", name))
+    }
+}
+
+// The depth `path` reaches below `base`, a directory within the
+// destination, if it stays inside the destination: no absolute paths and
+// no more `..` than there are directories to climb out of.
+fn depth(base: usize, path: &Path) -> Option<usize> {
+    let mut depth = base;
+    for component in path.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => (),
+            Component::ParentDir => depth = depth.checked_sub(1)?,
+            Component::RootDir | Component::Prefix(_) => return None
+        }
+    }
+    Some(depth)
+}
+
+// Refuse entries that would be written, or link to, outside of the
+// destination directory, rather than trust a tampered archive.
+fn check_entry(path: &Path, entry_type: EntryType, link: Option<&Path>)
+    -> Result<(), String> {
+    let inside = path.components().all(|component| {
+        matches!(component, Component::Normal(_) | Component::CurDir)
+    });
+    if !inside {
+        return Err(String::from("the path leaves the destination directory"));
+    }
+
+    let link = match link {
+        Some(link) => link,
+        None => return Ok(())
+    };
+    // Symbolic links are relative to the entry's directory, hard links to
+    // the top of the archive.
+    let base = if entry_type == EntryType::Symlink {
+        path.parent().map_or(0, |parent| depth(0, parent).unwrap_or(0))
+    } else {
+        0
+    };
+    match depth(base, link) {
+        Some(_) => Ok(()),
+        None => Err(format!("it links to {}, outside of the destination \
+                             directory", link.display()))
+    }
+}
+
+pub fn extract(archive: &Path, dest: &Path) -> Result<(), String> {
+    let file = File::open(archive).map_err(|e| {
+        format!("unable to open {}: {}", archive.display(), e)
+    })?;
+    let mut tar = Archive::new(decoder(archive, file)?);
+    tar.set_preserve_permissions(true);
+    tar.set_preserve_mtime(true);
+
+    let entries = tar.entries().map_err(|e| {
+        format!("unable to read {}: {}", archive.display(), e)
+    })?;
+
+    for (count, entry) in entries.enumerate() {
+        let mut entry = entry.map_err(|e| {
+            format!("error reading {} after {} entries: {}",
+                    archive.display(), count, e)
+        })?;
+
+        let path = entry.path()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| String::from("<invalid path>"));
+        let link = entry.link_name()
+            .map_err(|e| format!("invalid link name for {} in {}: {}",
+                                 path, archive.display(), e))?;
+        let entry_path = entry.path().map_err(|e| {
+            format!("invalid path in {}: {}", archive.display(), e)
+        })?;
+        check_entry(&entry_path, entry.header().entry_type(), link.as_deref())
+            .map_err(|e| format!("refusing to extract {} from {}: {}",
+                                 path, archive.display(), e))?;
+
+        let unpacked = entry.unpack_in(dest).map_err(|e| {
+            format!("error extracting {} from {} after {} entries: {}",
+                    path, archive.display(), count, e)
+        })?;
+        if !unpacked {
+            return Err(format!("refusing to extract {} from {}: it would \
+                                be written outside of {}", path,
+                               archive.display(), dest.display()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_entry;
+    use tar::EntryType;
+    use std::path::Path;
+
+    fn file(path: &str) -> Result<(), String> {
+        check_entry(Path::new(path), EntryType::Regular, None)
+    }
+
+    fn symlink(path: &str, link: &str) -> Result<(), String> {
+        check_entry(Path::new(path), EntryType::Symlink,
+                    Some(Path::new(link)))
+    }
+
+    fn hardlink(path: &str, link: &str) -> Result<(), String> {
+        check_entry(Path::new(path), EntryType::Link, Some(Path::new(link)))
+    }
+
+    #[test]
+    fn accepts_files_inside() {
+        assert!(file("newt-0.52.24/newt.c").is_ok());
+        assert!(file("./newt-0.52.24/po/de.po").is_ok());
+    }
+
+    #[test]
+    fn rejects_parent_dir_paths() {
+        assert!(file("../newt.c").is_err());
+        assert!(file("newt-0.52.24/../../newt.c").is_err());
+        // Even where it would stay inside, `..` isn't needed by a release.
+        assert!(file("newt-0.52.24/../newt.c").is_err());
+    }
+
+    #[test]
+    fn rejects_absolute_paths() {
+        assert!(file("/etc/passwd").is_err());
+        assert!(symlink("/tmp/link", "target").is_err());
+    }
+
+    #[test]
+    fn accepts_relative_symlinks_inside() {
+        assert!(symlink("lib/libnewt.so", "libnewt.so.0.52").is_ok());
+        assert!(symlink("lib/pkgconfig/libnewt.pc", "../../share/newt.pc")
+            .is_ok());
+        assert!(symlink("newt-0.52.24/link", "./newt.c").is_ok());
+    }
+
+    #[test]
+    fn rejects_escaping_symlinks() {
+        assert!(symlink("link", "../outside").is_err());
+        assert!(symlink("lib/libnewt.so", "../../outside").is_err());
+        assert!(symlink("lib/libnewt.so", "a/../../../outside").is_err());
+        assert!(symlink("lib/libnewt.so", "/usr/lib/libnewt.so").is_err());
+    }
+
+    #[test]
+    fn resolves_hardlinks_from_the_top() {
+        assert!(hardlink("newt-0.52.24/b", "newt-0.52.24/a").is_ok());
+        // Relative to the entry's directory this would stay inside.
+        assert!(hardlink("newt-0.52.24/b", "../a").is_err());
+        assert!(hardlink("newt-0.52.24/b", "/etc/passwd").is_err());
+    }
+}
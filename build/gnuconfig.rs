@@ -0,0 +1,68 @@
+use build_config::env_var;
+
+use std::fs;
+use std::path::{Path,PathBuf};
+use std::process::Command;
+
+const SCRIPTS: [&str; 2] = ["config.guess", "config.sub"];
+
+fn has_scripts(dir: &Path) -> bool {
+    SCRIPTS.iter().all(|script| dir.join(script).is_file())
+}
+
+fn automake_dir() -> Option<PathBuf> {
+    let output = Command::new("automake")
+        .arg("--print-libdir")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    if has_scripts(&dir) { Some(dir) } else { None }
+}
+
+// The directory to take config.guess and config.sub from instead of the
+// copies shipped in the archives, if any.
+pub fn source_dir() -> Option<PathBuf> {
+    if let Some(dir) = env_var("NEWT_SYS_GNUCONFIG_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+
+    if cfg!(feature = "system-gnuconfig") {
+        automake_dir()
+    } else {
+        None
+    }
+}
+
+// Replace every config.guess and config.sub in `src_dir` and its immediate
+// subdirectories with the ones from `gnuconfig_dir`.
+pub fn refresh(src_dir: &Path, gnuconfig_dir: &Path) -> Result<(), String> {
+    if !has_scripts(gnuconfig_dir) {
+        return Err(format!("{} doesn't contain config.guess and config.sub",
+                           gnuconfig_dir.display()));
+    }
+
+    let mut dirs = vec![src_dir.to_path_buf()];
+    let entries = fs::read_dir(src_dir)
+        .map_err(|e| format!("unable to read {}: {}", src_dir.display(), e))?;
+    for entry in entries.flatten() {
+        if entry.path().is_dir() {
+            dirs.push(entry.path());
+        }
+    }
+
+    for dir in dirs.iter() {
+        for script in SCRIPTS.iter() {
+            let dest = dir.join(script);
+            if dest.is_file() {
+                fs::copy(gnuconfig_dir.join(script), &dest).map_err(|e| {
+                    format!("unable to replace {}: {}", dest.display(), e)
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,621 @@
+use build_config::{self,env_var};
+use lazy_static::lazy_static;
+
+use std::env;
+use std::ffi::{OsStr,OsString};
+use std::path::{Path,PathBuf};
+use std::process::Command;
+
+// The names libnewt's .pc file is installed under, most common first. Some
+// distributions call it newt.pc.
+pub const PC_NAMES: &[&str] = &["libnewt", "newt"];
+
+pub struct Library {
+    pub include_paths: Vec<PathBuf>,
+    pub link_paths: Vec<PathBuf>,
+    pub libs: Vec<String>,
+    pub version: Option<String>,
+    pub root: Option<PathBuf>,
+    // The names and versions of the libraries its .pc file requires, when
+    // it was found with pkg-config.
+    pub requires: Vec<(String, String)>
+}
+
+impl From<pkg_config::Library> for Library {
+    fn from(lib: pkg_config::Library) -> Library {
+        let mut link_paths: Vec<PathBuf> = Vec::new();
+        for path in lib.link_paths {
+            if !link_paths.contains(&path) {
+                link_paths.push(path);
+            }
+        }
+        Library {
+            include_paths: lib.include_paths,
+            link_paths,
+            libs: lib.libs,
+            version: Some(lib.version),
+            root: None,
+            requires: Vec::new()
+        }
+    }
+}
+
+#[cfg(feature = "vcpkg")]
+impl From<vcpkg::Library> for Library {
+    fn from(lib: vcpkg::Library) -> Library {
+        Library {
+            include_paths: lib.include_paths,
+            link_paths: lib.link_paths,
+            libs: lib.found_names,
+            version: None,
+            root: None,
+            requires: Vec::new()
+        }
+    }
+}
+
+// The C library, the libraries that go with it on some systems and the
+// toolchain's own, always linked as the system has them.
+const SYSTEM_LIBS: &[&str] = &["c", "m", "dl", "pthread", "rt", "util",
+                               "intl", "iconv", "gcc", "gcc_s", "stdc++",
+                               "c++"];
+
+// The target's sysroot, given with PKG_CONFIG_SYSROOT_DIR or CROSS_SYSROOT.
+fn sysroot() -> Option<String> {
+    build_config::target_var("PKG_CONFIG_SYSROOT_DIR")
+        .or_else(build_config::cross_sysroot)
+        .filter(|sysroot| !sysroot.is_empty())
+}
+
+// Whether `dir` belongs to the system or the sysroot rather than to this
+// build or a separate installation.
+fn is_system_dir(dir: &Path) -> bool {
+    let build_dirs = [env::var_os("OUT_DIR"),
+                      env_var("NEWT_SYS_CACHE_DIR").map(OsString::from)];
+    if build_dirs.iter().flatten()
+        .any(|build_dir| !build_dir.is_empty() && dir.starts_with(build_dir)) {
+        return false;
+    }
+
+    let sysroot = sysroot();
+    ["/usr", "/lib", "/lib64"].iter().map(PathBuf::from)
+        .chain(sysroot.map(PathBuf::from))
+        .any(|root| dir.starts_with(root))
+}
+
+fn join_paths(paths: &[PathBuf], fallback: Option<PathBuf>) -> Option<String> {
+    let paths: Vec<PathBuf> = if paths.is_empty() {
+        fallback.into_iter().collect()
+    } else {
+        paths.to_vec()
+    };
+
+    if paths.is_empty() {
+        return None;
+    }
+
+    env::join_paths(paths).ok()
+        .map(|paths| paths.to_string_lossy().into_owned())
+}
+
+impl Library {
+    fn add_flags(&mut self, flags: &str) {
+        for flag in flags.split_whitespace() {
+            if let Some(path) = flag.strip_prefix("-I") {
+                self.include_paths.push(PathBuf::from(path));
+            } else if let Some(path) = flag.strip_prefix("-L") {
+                let path = PathBuf::from(path);
+                if !self.link_paths.contains(&path) {
+                    self.link_paths.push(path);
+                }
+            } else if let Some(lib) = flag.strip_prefix("-l") {
+                self.libs.push(lib.to_string());
+            }
+        }
+    }
+
+    pub fn is_static(&self, lib: &str) -> bool {
+        self.archive_dir(lib).is_some()
+    }
+
+    // The first link path with a lib<lib>.a.
+    fn archive_dir(&self, lib: &str) -> Option<&Path> {
+        let archive = format!("lib{}.a", lib);
+        self.link_paths.iter()
+            .find(|path| path.join(&archive).exists())
+            .map(|path| path.as_path())
+    }
+
+    // Whether `lib` is linked with static=. libnewt is whenever there's an
+    // archive of it, and slang when its archive is next to libnewt's. The
+    // others are only when their archive isn't the system's, as with the
+    // pkg-config crate: the system's libm.a is an ld script on glibc, which
+    // rustc can't bundle.
+    pub fn links_static(&self, lib: &str) -> bool {
+        if SYSTEM_LIBS.contains(&lib) {
+            return false;
+        }
+
+        let dir = match self.archive_dir(lib) {
+            Some(dir) => dir,
+            None => return false
+        };
+        match lib {
+            "newt" => true,
+            "slang" if self.archive_dir("newt") == Some(dir) => true,
+            _ => !is_system_dir(dir)
+        }
+    }
+
+    // The cargo: lines linking the library, static ones if `statik`.
+    pub fn cargo_metadata(&self, statik: bool) -> Vec<String> {
+        let mut lines: Vec<String> = self.link_paths.iter()
+            .map(|path| format!("cargo:rustc-link-search=native={}",
+                                path.display()))
+            .collect();
+
+        for lib in self.libs.iter() {
+            if statik && self.links_static(lib) {
+                lines.push(format!("cargo:rustc-link-lib=static={}", lib));
+            } else {
+                lines.push(format!("cargo:rustc-link-lib={}", lib));
+            }
+        }
+        lines
+    }
+
+    pub fn emit_cargo_metadata(&self, statik: bool) {
+        for line in self.cargo_metadata(statik) {
+            println!("{}", line);
+        }
+    }
+
+    pub fn emit_dep_metadata(&self) {
+        self.emit_prefixed_metadata("");
+    }
+
+    pub fn emit_package_metadata(&self, package: &str) {
+        self.emit_prefixed_metadata(&format!("{}_", package));
+    }
+
+    fn emit_prefixed_metadata(&self, prefix: &str) {
+        if let Some(ref root) = self.root {
+            println!("cargo:{}root={}", prefix, root.display());
+        }
+
+        let include = self.root.as_ref().map(|root| root.join("include"));
+        if let Some(include) = join_paths(&self.include_paths, include) {
+            println!("cargo:{}include={}", prefix, include);
+        }
+
+        let lib = self.root.as_ref().map(|root| root.join("lib"));
+        if let Some(lib) = join_paths(&self.link_paths, lib) {
+            println!("cargo:{}lib={}", prefix, lib);
+        }
+
+        if let Some(ref version) = self.version {
+            println!("cargo:{}version={}", prefix, version);
+        }
+    }
+}
+
+pub fn from_env_dirs(statik: bool) -> Option<Library> {
+    let lib_dir = env_var("NEWT_LIB_DIR");
+    let include_dir = env_var("NEWT_INCLUDE_DIR");
+    if lib_dir.is_none() && include_dir.is_none() {
+        return None;
+    }
+    Some(from_dirs(lib_dir, include_dir, statik))
+}
+
+// The installation prefix given with NEWT_PREFIX.
+pub fn newt_prefix() -> Option<PathBuf> {
+    env_var("NEWT_PREFIX").filter(|p| !p.is_empty()).map(PathBuf::from)
+}
+
+// The pkg-config directories of an installation prefix that exist.
+pub fn prefix_pkg_config_dirs(prefix: &Path) -> Vec<PathBuf> {
+    ["lib/pkgconfig", "lib64/pkgconfig", "share/pkgconfig"].iter()
+        .map(|dir| prefix.join(dir))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+// A libnewt installed into `prefix` without a libnewt.pc, found in its lib
+// or lib64 and include directories.
+pub fn from_prefix(prefix: &Path, statik: bool) -> Result<Library, String> {
+    let names = ["libnewt.a", "libnewt.so", "libnewt.dylib", "newt.lib"];
+    let lib_dir = ["lib", "lib64"].iter()
+        .map(|dir| prefix.join(dir))
+        .find(|dir| names.iter().any(|name| dir.join(name).is_file()))
+        .ok_or_else(|| format!("there is no libnewt in {}/lib",
+                               prefix.display()))?;
+
+    let include_dir = prefix.join("include");
+    let include_dir = Some(include_dir)
+        .filter(|dir| dir.join("newt.h").is_file());
+    let path_string = |path: PathBuf| path.to_string_lossy().into_owned();
+    let mut library = from_dirs(Some(path_string(lib_dir)),
+                                include_dir.map(path_string), statik);
+    library.root = Some(prefix.to_path_buf());
+    Ok(library)
+}
+
+// The Debian multiarch directory name for the target, the target without
+// its vendor.
+fn multiarch() -> String {
+    let target = build_config::rust_target();
+    let parts: Vec<&str> = target.split('-').collect();
+    match parts.len() {
+        4 => format!("{}-{}-{}", parts[0], parts[2], parts[3]),
+        _ => target
+    }
+}
+
+// A static libnewt.a installed without a .pc file, as some SDKs ship it,
+// found in the library directories of the system or of the sysroot given
+// with PKG_CONFIG_SYSROOT_DIR or CROSS_SYSROOT. A cross build without a
+// sysroot would only find the host's.
+pub fn find_archive() -> Option<Library> {
+    let sysroot = sysroot();
+    let root = match sysroot {
+        Some(ref sysroot) => PathBuf::from(sysroot),
+        None if !build_config::is_cross() => PathBuf::from("/"),
+        None => return None
+    };
+
+    let mut prefixes = vec![root.join("usr/local"), root.join("usr")];
+    if let Some(prefix) = build_config::local_prefix() {
+        prefixes.push(root.join(prefix.trim_start_matches('/')));
+    }
+    let lib_dirs = [format!("lib/{}", multiarch()), String::from("lib64"),
+                    String::from("lib")];
+    let (prefix, lib_dir) = prefixes.iter()
+        .flat_map(|prefix| {
+            lib_dirs.iter().map(move |dir| (prefix, prefix.join(dir)))
+        })
+        .find(|(_, dir)| dir.join("libnewt.a").is_file())?;
+
+    let include_dir = Some(prefix.join("include"))
+        .filter(|dir| dir.join("newt.h").is_file());
+    let path_string = |path: PathBuf| path.to_string_lossy().into_owned();
+    let mut library = from_dirs(Some(path_string(lib_dir)),
+                                include_dir.map(path_string), true);
+    library.root = Some(prefix.clone());
+    Some(library)
+}
+
+fn from_dirs(lib_dir: Option<String>, include_dir: Option<String>,
+             statik: bool) -> Library {
+    let mut library = Library {
+        include_paths: include_dir.into_iter().map(PathBuf::from).collect(),
+        link_paths: lib_dir.iter().map(PathBuf::from).collect(),
+        libs: vec![String::from("newt")],
+        version: None,
+        root: None,
+        requires: Vec::new()
+    };
+
+    if statik && library.is_static("newt") {
+        // Use the libnewt.pc (or newt.pc) next to the library when there is
+        // one.
+        let pc_dir = lib_dir.as_ref()
+            .map(|dir| Path::new(dir).join("pkgconfig"));
+        let flags = pc_dir.and_then(|dir| {
+            let name = pc_name_in(&dir)?;
+            let mut paths = vec![dir];
+            if let Some(path) = env::var_os("PKG_CONFIG_PATH") {
+                paths.extend(env::split_paths(&path));
+            }
+            let search_path = env::join_paths(paths).ok()?;
+            pkg_config(name, Search::Prepend(&search_path),
+                       &["--static", "--libs"]).ok()
+        });
+
+        match flags {
+            Some(flags) => {
+                library.libs.clear();
+                library.add_flags(&flags);
+            },
+            None => add_static_deps(&mut library)
+        }
+    }
+    library
+}
+
+// What a static libnewt without a .pc file needs: slang and the libraries
+// slang uses.
+fn add_static_deps(library: &mut Library) {
+    library.libs.push(String::from("slang"));
+
+    // The BSD packages build newt with NLS enabled.
+    if let Some(prefix) = build_config::local_prefix() {
+        library.link_paths.push(Path::new(prefix).join("lib"));
+        library.libs.push(String::from("intl"));
+        if build_config::needs_libiconv() {
+            library.libs.push(String::from("iconv"));
+        }
+    }
+
+    library.libs.push(String::from("m"));
+    if build_config::rust_target().contains("-linux") {
+        library.libs.push(String::from("dl"));
+    }
+}
+
+// cross images point PKG_CONFIG_PATH at the target's libraries. If
+// CROSS_SYSROOT has .pc files of its own and pkg-config hasn't been given a
+// sysroot, its .pc directories are searched and the libraries found there
+// are used from inside it.
+fn cross_container_sysroot() -> Option<(PathBuf, Vec<PathBuf>)> {
+    if !build_config::is_cross() || !build_config::in_cross_container() ||
+       build_config::target_var("PKG_CONFIG_SYSROOT_DIR").is_some() {
+        return None;
+    }
+
+    let sysroot = PathBuf::from(build_config::cross_sysroot()?);
+    let dirs: Vec<PathBuf> = ["usr/lib/pkgconfig", "usr/share/pkgconfig",
+                              "lib/pkgconfig"].iter()
+        .map(|dir| sysroot.join(dir))
+        .filter(|dir| dir.is_dir())
+        .collect();
+    if dirs.is_empty() {
+        return None;
+    }
+    Some((sysroot, dirs))
+}
+
+// The directories pkg-config searches for the system's libraries on top of
+// its own: NEWT_PREFIX's, those of the BSD package collections and
+// Homebrew, which pkg-config isn't always configured to search, and the
+// cross image's sysroot.
+fn system_pc_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(prefix) = newt_prefix() {
+        dirs.extend(prefix_pkg_config_dirs(&prefix));
+    }
+    if !build_config::is_cross() {
+        dirs.extend(build_config::pkg_config_dirs());
+    }
+    if let Some((_, ref sysroot_dirs)) = *CROSS_CONTAINER_SYSROOT {
+        dirs.extend(sysroot_dirs.iter().cloned());
+    }
+    dirs
+}
+
+lazy_static! {
+    static ref CROSS_CONTAINER_SYSROOT: Option<(PathBuf, Vec<PathBuf>)> =
+        cross_container_sysroot();
+    // Finding Homebrew's directories runs brew, so this is only done once.
+    static ref SYSTEM_PC_DIRS: Vec<PathBuf> = system_pc_dirs();
+}
+
+// The pkg-config arguments adding SYSTEM_PC_DIRS to its search path. They
+// are passed to each command rather than set in PKG_CONFIG_PATH, which the
+// configure scripts of the bundled libraries would see as well.
+fn system_search_args() -> Vec<OsString> {
+    SYSTEM_PC_DIRS.iter()
+        .map(|dir| {
+            let mut arg = OsString::from("--with-path=");
+            arg.push(dir);
+            arg
+        })
+        .collect()
+}
+
+// What pkg-config is asked for to find the system library `name`. --with-path
+// directories come after PKG_CONFIG_PATH, so a .pc file in NEWT_PREFIX,
+// which has to come before any others, is given by its path.
+fn system_module(name: &str) -> String {
+    newt_prefix()
+        .and_then(|prefix| {
+            prefix_pkg_config_dirs(&prefix).into_iter()
+                .map(|dir| dir.join(format!("{}.pc", name)))
+                .find(|pc| pc.is_file())
+        })
+        .map(|pc| pc.display().to_string())
+        .unwrap_or_else(|| name.to_string())
+}
+
+// The cross image's sysroot, if the system library `name` was found in it.
+// pkg-config isn't given the sysroot, which would apply to the host's .pc
+// files as well, so the paths of such a library are moved into it here.
+fn cross_sysroot_of(name: &str) -> Option<&'static Path> {
+    let sysroot = match *CROSS_CONTAINER_SYSROOT {
+        Some((ref sysroot, _)) => sysroot,
+        None => return None
+    };
+    system_variable(name, "pcfiledir").ok()
+        .filter(|dir| Path::new(dir).starts_with(sysroot))
+        .map(|_| sysroot.as_path())
+}
+
+fn in_sysroot(sysroot: Option<&Path>, path: PathBuf) -> PathBuf {
+    match (sysroot, path.strip_prefix("/")) {
+        (Some(sysroot), Ok(relative)) if !path.starts_with(sysroot) =>
+            sysroot.join(relative),
+        _ => path
+    }
+}
+
+// Where pkg-config looks for .pc files.
+#[derive(Clone,Copy)]
+enum Search<'a> {
+    // Its default directories, the target's PKG_CONFIG_PATH and the
+    // directories of system_pc_dirs().
+    System,
+    // `path` ahead of those.
+    Prepend(&'a OsStr),
+    // Only `path`, for the bundled libraries.
+    Only(&'a str)
+}
+
+fn pkg_config(name: &str, search: Search, args: &[&str])
+    -> Result<String, String> {
+    let mut cmd = Command::new(build_config::pkg_config_command());
+    match search {
+        Search::System => {
+            for var in ["PKG_CONFIG_PATH", "PKG_CONFIG_LIBDIR",
+                        "PKG_CONFIG_SYSROOT_DIR"].iter() {
+                if let Some(val) = build_config::target_var(var) {
+                    cmd.env(var, val);
+                }
+            }
+            cmd.args(system_search_args());
+        },
+        Search::Prepend(path) => {
+            cmd.env("PKG_CONFIG_PATH", path);
+        },
+        // Only look at the given directories, a sysroot for the target's
+        // system libraries doesn't apply to them.
+        Search::Only(path) => {
+            cmd.env("PKG_CONFIG_PATH", path)
+                .env("PKG_CONFIG_LIBDIR", path)
+                .env_remove("PKG_CONFIG_SYSROOT_DIR");
+        }
+    }
+
+    let output = cmd.args(args)
+        .arg(name)
+        .output()
+        .map_err(|e| format!("error running pkg-config: {}", e))?;
+
+    if !output.status.success() {
+        // --atleast-version and --exists fail without saying why.
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = if stderr.trim().is_empty() {
+            String::new()
+        } else {
+            format!(": {}", stderr)
+        };
+        return Err(format!("pkg-config {} {} failed{}", args.join(" "),
+                           name, detail));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// The name of libnewt's .pc file in `dir`, if it has one.
+pub fn pc_name_in(dir: &Path) -> Option<&'static str> {
+    PC_NAMES.iter().cloned()
+        .find(|name| dir.join(format!("{}.pc", name)).is_file())
+}
+
+// The name pkg-config knows the system libnewt by, if it knows it at all.
+pub fn system_pc_name() -> Option<&'static str> {
+    PC_NAMES.iter().cloned()
+        .find(|name| {
+            pkg_config(&system_module(name), Search::System, &["--exists"])
+                .is_ok()
+        })
+}
+
+// The value of `var` in the .pc file of the system library `name`.
+pub fn system_variable(name: &str, var: &str) -> Result<String, String> {
+    pkg_config(&system_module(name), Search::System,
+               &[&format!("--variable={}", var)])
+}
+
+// The version of the system library `name`, if pkg-config knows it.
+pub fn system_version(name: &str) -> Option<String> {
+    pkg_config(&system_module(name), Search::System, &["--modversion"]).ok()
+}
+
+// The versions of the libraries the .pc file of the system library `name`
+// requires, as pkg-config finds them when probing it.
+pub fn system_requires(name: &str) -> Vec<(String, String)> {
+    let module = system_module(name);
+    let mut requires: Vec<(String, String)> = Vec::new();
+    for arg in ["--print-requires", "--print-requires-private"].iter() {
+        let output = pkg_config(&module, Search::System, &[arg])
+            .unwrap_or_default();
+        let deps = output.lines()
+            .filter_map(|line| line.split_whitespace().next());
+        for dep in deps {
+            if requires.iter().any(|(name, _)| name == dep) {
+                continue;
+            }
+            if let Ok(version) = pkg_config(dep, Search::System,
+                                            &["--modversion"]) {
+                requires.push((dep.to_string(), version));
+            }
+        }
+    }
+    requires
+}
+
+fn probe(name: &str, version: &str, search: Search)
+    -> Result<Library, String> {
+    let atleast = format!("--atleast-version={}", version);
+    pkg_config(name, search, &[&atleast])?;
+
+    let prefix = pkg_config(name, search, &["--variable=prefix"])?;
+    let mut library = Library {
+        include_paths: Vec::new(),
+        link_paths: Vec::new(),
+        libs: Vec::new(),
+        version: Some(pkg_config(name, search, &["--modversion"])?),
+        root: Some(PathBuf::from(prefix)),
+        requires: Vec::new()
+    };
+
+    let flags = pkg_config(name, search,
+                           &["--static", "--libs", "--cflags"])?;
+    library.add_flags(&flags);
+    Ok(library)
+}
+
+pub fn probe_installed(name: &str, version: &str, search_paths: &[&Path])
+    -> Result<Library, String> {
+    let search_path = env::join_paths(search_paths)
+        .map(|path| path.to_string_lossy().into_owned())
+        .map_err(|e| format!("invalid pkg-config search path: {}", e))?;
+    probe(name, version, Search::Only(&search_path))
+}
+
+// A system library, at least `version` if given, with what `pkg-config
+// --static` says it needs if `statik`.
+pub fn probe_system(name: &str, version: Option<&str>, statik: bool)
+    -> Result<Library, String> {
+    let mut config = pkg_config::Config::new();
+    config.cargo_metadata(false)
+        .env_metadata(true)
+        .statik(statik);
+    for arg in system_search_args() {
+        config.arg(arg);
+    }
+    if let Some(version) = version {
+        config.atleast_version(version);
+    }
+
+    let mut library = config.probe(&system_module(name))
+        .map(Library::from)
+        .map_err(|e| e.to_string())?;
+    let sysroot = cross_sysroot_of(name);
+    library.include_paths = library.include_paths.into_iter()
+        .map(|path| in_sysroot(sysroot, path))
+        .collect();
+    library.link_paths = library.link_paths.into_iter()
+        .map(|path| in_sysroot(sysroot, path))
+        .collect();
+    library.root = system_variable(name, "prefix").ok()
+        .map(|prefix| in_sysroot(sysroot, PathBuf::from(prefix)));
+    Ok(library)
+}
+
+// A system library for static linking, with everything `pkg-config
+// --static` says it needs. Fails if there's no static archive of it.
+pub fn probe_static(name: &str, lib: &str, version: &str)
+    -> Result<Library, String> {
+    let mut library = probe_system(name, Some(version), true)?;
+    let libdir = PathBuf::from(system_variable(name, "libdir")?);
+    let libdir = in_sysroot(cross_sysroot_of(name), libdir);
+    if !library.link_paths.contains(&libdir) {
+        library.link_paths.push(libdir.clone());
+    }
+
+    if !library.is_static(lib) {
+        return Err(format!("there is no lib{}.a in {}", lib,
+                           libdir.display()));
+    }
+    Ok(library)
+}
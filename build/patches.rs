@@ -0,0 +1,54 @@
+use build_config::env_var;
+use checksum;
+
+use std::env;
+use std::fs;
+use std::path::{Path,PathBuf};
+
+// patches/<package> in the crate, then <package> in NEWT_SYS_PATCH_DIR.
+fn dirs(package: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![
+        PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap())
+            .join("patches").join(package)
+    ];
+
+    if let Some(dir) = env_var("NEWT_SYS_PATCH_DIR") {
+        dirs.push(Path::new(&dir).join(package));
+    }
+    dirs
+}
+
+// The *.patch files to apply to `package`, in the order they are applied:
+// by directory, then by file name.
+pub fn find(package: &str) -> Result<Vec<PathBuf>, String> {
+    let mut patches = Vec::new();
+
+    for dir in dirs(package).iter().filter(|dir| dir.is_dir()) {
+        println!("cargo:rerun-if-changed={}", dir.display());
+        let entries = fs::read_dir(dir)
+            .map_err(|e| format!("unable to read {}: {}", dir.display(), e))?;
+
+        let mut found: Vec<PathBuf> = entries.flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file() &&
+                path.extension().is_some_and(|ext| ext == "patch")
+            })
+            .collect();
+        found.sort();
+        patches.extend(found);
+    }
+    Ok(patches)
+}
+
+// Names and checksums of `patches` for the build stamp.
+pub fn fingerprint(patches: &[PathBuf]) -> Result<String, String> {
+    let mut parts = Vec::new();
+    for patch in patches.iter() {
+        let path = patch.display().to_string();
+        let digest = checksum::sha256(patch)
+            .map_err(|e| format!("unable to read {}: {}", path, e))?;
+        parts.push(format!("{}:{}", path, digest));
+    }
+    Ok(parts.join(" "))
+}
@@ -0,0 +1,133 @@
+use artifacts;
+use build_config::{self,env_var};
+use checksum;
+use download;
+use error::{BuildError,Phase};
+use extract;
+use stamp::Stamp;
+
+use std::fs;
+use std::path::Path;
+
+// The targets bundles are looked for. Everything else is built from source.
+const TARGETS: &[&str] = &[
+    "x86_64-unknown-linux-gnu",
+    "x86_64-unknown-linux-musl",
+    "aarch64-unknown-linux-gnu",
+    "aarch64-unknown-linux-musl"
+];
+
+const STAMP_FILE: &str = ".newt-sys-prebuilt";
+
+// The name of the bundle of `package` for the cargo target, if there can be
+// one.
+fn bundle_name(package: &str, version: &str) -> Option<String> {
+    let target = build_config::rust_target();
+    if !TARGETS.contains(&target.as_str()) {
+        return None;
+    }
+    Some(format!("{}-{}-{}.tar.gz", package, version, target))
+}
+
+// The SHA-256 `bundle` is pinned to in NEWT_SYS_BUNDLE_CACHE_SHA256SUMS, a
+// file in the format written by sha256sum.
+fn pinned_sha256(bundle: &str) -> Result<Option<String>, String> {
+    let sums = match env_var("NEWT_SYS_BUNDLE_CACHE_SHA256SUMS") {
+        Some(ref sums) if !sums.is_empty() => sums.clone(),
+        _ => return Ok(None)
+    };
+    println!("cargo:rerun-if-changed={}", sums);
+    let contents = fs::read_to_string(&sums)
+        .map_err(|e| format!("unable to read {}: {}", sums, e))?;
+
+    Ok(contents.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let sha256 = fields.next()?;
+        let name = fields.next()?.trim_start_matches('*');
+        if name == bundle {
+            Some(sha256.to_ascii_lowercase())
+        } else {
+            None
+        }
+    }))
+}
+
+// Install the prebuilt bundle of `package` into `prefix`, downloading it
+// from the user's bundle cache at NEWT_SYS_BUNDLE_CACHE_URL; newt-sys
+// doesn't publish any. Returns false, for the package to be built from
+// source, if there is no bundle for the target or it can't be downloaded;
+// one that doesn't match its pinned checksum is an error.
+pub fn install(package: &str, version: &str, out_dir: &Path, prefix: &Path)
+    -> Result<bool, BuildError> {
+    let bundle = match bundle_name(package, version) {
+        Some(bundle) => bundle,
+        None => return Ok(false)
+    };
+    let base_url = match env_var("NEWT_SYS_BUNDLE_CACHE_URL") {
+        Some(ref url) if !url.is_empty() => url.trim_end_matches('/')
+            .to_string(),
+        _ => {
+            println!("cargo:warning=NEWT_SYS_BUNDLE_CACHE_URL isn't set, \
+                      building {} from source", package);
+            return Ok(false);
+        }
+    };
+    let sha256 = match pinned_sha256(&bundle)
+        .map_err(|e| BuildError::new(package, Phase::Verify, e))? {
+        Some(sha256) => sha256,
+        None => {
+            println!("cargo:warning=no checksum for {} in \
+                      NEWT_SYS_BUNDLE_CACHE_SHA256SUMS, building {} from \
+                      source", bundle, package);
+            return Ok(false);
+        }
+    };
+
+    let stamp = Stamp::new(&prefix.join(STAMP_FILE), &[
+        ("bundle", &bundle),
+        ("sha256", &sha256)
+    ]);
+    if stamp.is_current() {
+        return Ok(true);
+    }
+
+    let urls = vec![format!("{}/{}", base_url, bundle)];
+    let archive = match download::download_from(package, &urls, &bundle,
+                                                 &sha256,
+                                                 &out_dir.join("download")) {
+        Ok(archive) => archive,
+        Err(ref e) if e.phase() == Phase::Download => {
+            let reason = if build_config::offline() {
+                "cargo is offline"
+            } else {
+                "it couldn't be downloaded"
+            };
+            println!("cargo:warning=not using {}: {}, building {} from \
+                      source", bundle, reason, package);
+            return Ok(false);
+        },
+        Err(e) => return Err(e)
+    };
+    // A bundle left in the download directory isn't checked on the way.
+    checksum::verify(&archive, &sha256)
+        .map_err(|e| BuildError::new(package, Phase::Verify, e))?;
+
+    let _ = fs::remove_dir_all(prefix);
+    fs::create_dir_all(prefix).map_err(|e| {
+        BuildError::new(package, Phase::Extract, format!(
+            "unable to create {}: {}", prefix.display(), e))
+    })?;
+    extract::extract(&archive, prefix)
+        .map_err(|e| BuildError::new(package, Phase::Extract, e))?;
+    // The .pc files of a bundle locate the installation relative to
+    // themselves, so they aren't compared against `prefix`.
+    artifacts::check(package, prefix, None)
+        .map_err(|e| BuildError::new(package, Phase::Verify,
+                                     format!("{}: {}", bundle, e)))?;
+
+    stamp.write().map_err(|e| {
+        BuildError::new(package, Phase::Extract,
+                        format!("unable to write bundle stamp: {}", e))
+    })?;
+    Ok(true)
+}
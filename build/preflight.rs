@@ -0,0 +1,77 @@
+use std::ffi::OsStr;
+use std::process::{Command,Stdio};
+
+// A program the bundled build needs, and what for.
+pub struct Prerequisite {
+    pub tool: &'static str,
+    pub reason: &'static str
+}
+
+// Whether `program` can be executed at all. Its exit status doesn't matter,
+// not every tool knows --version.
+pub fn runs<S: AsRef<OsStr>>(program: S) -> bool {
+    Command::new(program)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+// Packages providing `tool` on Debian, Fedora, Homebrew, the BSDs and
+// MSYS2. Empty if it comes with the base system, a command if it isn't a
+// package.
+fn packages(tool: &str) -> [&'static str; 5] {
+    match tool {
+        "cc" => ["gcc", "gcc", "xcode-select --install", "",
+                 "mingw-w64-ucrt-x86_64-gcc"],
+        "make" => ["make", "make", "make", "gmake", "make"],
+        "pkg-config" => ["pkg-config", "pkgconf", "pkgconf", "pkgconf",
+                         "pkgconf"],
+        "patch" => ["patch", "patch", "", "", "patch"],
+        "curl" => ["curl", "curl", "", "curl", "curl"],
+        _ => ["", "", "", "", ""]
+    }
+}
+
+fn install_hint(tool: &str) -> String {
+    let [debian, fedora, brew, bsd, msys2] = packages(tool);
+    let command = |manager: &str, package: &str| match package {
+        "" => String::from("part of the base system, check PATH"),
+        package if package.contains(' ') => format!("`{}`", package),
+        package => format!("`{} {}`", manager, package)
+    };
+
+    if cfg!(target_os = "macos") {
+        command("brew install", brew)
+    } else if cfg!(target_os = "freebsd") {
+        command("pkg install", bsd)
+    } else if cfg!(target_os = "openbsd") {
+        command("pkg_add", bsd)
+    } else if cfg!(target_os = "netbsd") {
+        command("pkgin install", bsd)
+    } else if cfg!(windows) {
+        command("pacman -S", msys2)
+    } else {
+        format!("{} (Debian, Ubuntu) or {} (Fedora)",
+                command("apt-get install", debian),
+                command("dnf install", fedora))
+    }
+}
+
+// Describe every missing prerequisite with a way to install it on this
+// system, or None if nothing is missing.
+pub fn report(missing: &[Prerequisite]) -> Option<String> {
+    if missing.is_empty() {
+        return None;
+    }
+
+    let mut message = String::from("missing prerequisites:");
+    for prerequisite in missing.iter() {
+        message.push_str(&format!("\n    {} ({}): {}", prerequisite.tool,
+                                  prerequisite.reason,
+                                  install_hint(prerequisite.tool)));
+    }
+    Some(message)
+}
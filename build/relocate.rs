@@ -0,0 +1,43 @@
+use build_config;
+
+use std::fs;
+use std::path::Path;
+
+// The prefix of an installed .pc file, relative to the file itself so
+// that it still holds after the installation has been moved.
+const RELATIVE_PREFIX: &str = "${pcfiledir}/../..";
+
+fn relocate(pc: &Path, prefix: &str) -> Result<(), String> {
+    let contents = fs::read_to_string(pc)
+        .map_err(|e| format!("unable to read {}: {}", pc.display(), e))?;
+
+    let mut relocated = String::new();
+    for line in contents.lines() {
+        if line.starts_with("prefix=") {
+            relocated.push_str("prefix=");
+            relocated.push_str(RELATIVE_PREFIX);
+        } else {
+            relocated.push_str(&line.replace(prefix, "${prefix}"));
+        }
+        relocated.push('\n');
+    }
+
+    fs::write(pc, relocated)
+        .map_err(|e| format!("unable to write {}: {}", pc.display(), e))
+}
+
+// Rewrite the .pc files installed in `prefix` to locate the installation
+// from their own directory rather than by its absolute path.
+pub fn pkg_config_files(prefix: &Path) -> Result<(), String> {
+    let dir = prefix.join("lib/pkgconfig");
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| format!("unable to read {}: {}", dir.display(), e))?;
+    let prefix = build_config::shell_path(&prefix.display().to_string());
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "pc") {
+            relocate(&path, &prefix)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::fs;
+use std::path::Path;
+
+const MAGIC: &[u8] = b"!<arch>\n";
+const HEADER_LEN: usize = 60;
+
+// Overwrite a header field (padded with spaces) with `value`.
+fn set_field(header: &mut [u8], offset: usize, len: usize, value: &[u8]) {
+    let field = &mut header[offset..offset + len];
+    for byte in field.iter_mut() {
+        *byte = b' ';
+    }
+    field[..value.len()].copy_from_slice(value);
+}
+
+// Zero the timestamp, owner and group of every member of the `ar` archive
+// at `path` and give them all the same mode, as `ar D` would have.
+pub fn normalize_archive(path: &Path) -> Result<(), String> {
+    let mut data = fs::read(path)
+        .map_err(|e| format!("unable to read {}: {}", path.display(), e))?;
+    if !data.starts_with(MAGIC) {
+        return Err(format!("{} isn't an ar archive", path.display()));
+    }
+
+    let mut pos = MAGIC.len();
+    while pos + HEADER_LEN <= data.len() {
+        let header = &mut data[pos..pos + HEADER_LEN];
+        let size: usize = String::from_utf8_lossy(&header[48..58]).trim()
+            .parse()
+            .map_err(|_| format!("{} has a corrupt member header",
+                                 path.display()))?;
+
+        set_field(header, 16, 12, b"0");
+        set_field(header, 28, 6, b"0");
+        set_field(header, 34, 6, b"0");
+        set_field(header, 40, 8, b"644");
+
+        // Members are aligned to even offsets.
+        pos += HEADER_LEN + size + size % 2;
+    }
+
+    fs::write(path, data)
+        .map_err(|e| format!("unable to write {}: {}", path.display(), e))
+}
+
+// Normalize every static library in `dir`.
+pub fn normalize_archives(dir: &Path) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("unable to read {}: {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_type().is_ok_and(|kind| kind.is_file()) &&
+           path.extension().is_some_and(|ext| ext == "a") {
+            normalize_archive(&path)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use build_config::env_var;
+
+use std::env;
+use std::fs;
+use std::path::{Path,PathBuf};
+use std::time::UNIX_EPOCH;
+
+// An unpacked newt tree to build instead of the bundled archive.
+pub fn newt() -> Option<PathBuf> {
+    env_var("NEWT_SYS_SOURCE_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+// A git submodule in vendor/src with the sources of `package`, if it is
+// checked out.
+pub fn submodule(package: &str) -> Option<PathBuf> {
+    let src = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap())
+        .join("vendor/src");
+    // Only once there is something to watch, a missing path would have
+    // cargo rerun the build script every time.
+    if src.is_dir() {
+        println!("cargo:rerun-if-changed={}", src.display());
+    }
+
+    let dir = src.join(package);
+    let checked_out = ["configure.ac", "configure"].iter()
+        .any(|file| dir.join(file).is_file());
+    if checked_out { Some(dir) } else { None }
+}
+
+fn first_match<F>(dir: &Path, files: &[&str], find: F) -> Option<String>
+    where F: Fn(&str) -> Option<String> {
+    files.iter()
+        .filter_map(|file| fs::read_to_string(dir.join(file)).ok())
+        .find_map(|contents| contents.lines().find_map(&find))
+}
+
+// newt's version, from `VERSION=0.52.24` in configure.ac.
+fn newt_version(dir: &Path) -> Option<String> {
+    first_match(dir, &["configure.ac", "configure"], |line| {
+        let version = line.trim()
+            .strip_prefix("VERSION=")?
+            .trim_matches(|c| c == '"' || c == '\'');
+        Some(version.to_string()).filter(|version| !version.is_empty())
+    })
+}
+
+// popt's version, from `AC_INIT([popt], [1.19], ...)`.
+fn popt_version(dir: &Path) -> Option<String> {
+    first_match(dir, &["configure.ac"], |line| {
+        let args = line.trim().strip_prefix("AC_INIT(")?;
+        let version = args.split(',').nth(1)?
+            .trim()
+            .trim_matches(|c| c == '[' || c == ']');
+        Some(version.to_string()).filter(|version| !version.is_empty())
+    })
+}
+
+// slang's version, from `#define SLANG_VERSION_STRING "2.3.3"`.
+fn slang_version(dir: &Path) -> Option<String> {
+    first_match(dir, &["src/slang.h"], |line| {
+        let version = line.trim()
+            .strip_prefix("#define SLANG_VERSION_STRING")?
+            .trim()
+            .trim_matches('"');
+        Some(version.to_string()).filter(|version| !version.is_empty())
+    })
+}
+
+// The version of `package` in the tree at `dir`.
+pub fn version(package: &str, dir: &Path) -> Result<String, String> {
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+
+    let version = match package {
+        "newt" => newt_version(dir),
+        "popt" => popt_version(dir),
+        "slang" => slang_version(dir),
+        _ => panic!("Unexpected package requested to be built: {}", package)
+    };
+    version.ok_or_else(|| {
+        format!("unable to find the {} version in {}", package, dir.display())
+    })
+}
+
+fn skip(name: &str) -> bool {
+    name == ".git"
+}
+
+fn newest_mtime(dir: &Path, newest: &mut u64) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("unable to read {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let entry = entry
+            .map_err(|e| format!("unable to read {}: {}", dir.display(), e))?;
+        if skip(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+
+        let path = entry.path();
+        let metadata = fs::metadata(&path)
+            .map_err(|e| format!("unable to stat {}: {}", path.display(), e))?;
+        if metadata.is_dir() {
+            newest_mtime(&path, newest)?;
+        } else if let Ok(mtime) = metadata.modified() {
+            let secs = mtime.duration_since(UNIX_EPOCH)
+                .map(|age| age.as_secs())
+                .unwrap_or(0);
+            *newest = (*newest).max(secs);
+        }
+    }
+    Ok(())
+}
+
+// Identifies the state of the tree for the build stamp: its path and the
+// time of the most recent change to a file in it.
+pub fn fingerprint(dir: &Path) -> Result<String, String> {
+    let mut newest = 0;
+    newest_mtime(dir, &mut newest)?;
+    Ok(format!("{}@{}", dir.display(), newest))
+}
+
+// Copy the tree at `src` to `dest`, replacing whatever was there, so the
+// build never writes to the user's checkout.
+pub fn copy(src: &Path, dest: &Path) -> Result<(), String> {
+    if dest.exists() {
+        fs::remove_dir_all(dest).map_err(|e| {
+            format!("unable to remove {}: {}", dest.display(), e)
+        })?;
+    }
+    copy_dir(src, dest)
+}
+
+// Give `to` the modification time of `from`, so that make doesn't find
+// generated files such as configure older than their sources and try to
+// regenerate them. Left as is if that isn't possible.
+fn keep_mtime(from: &Path, to: &Path) {
+    let mtime = match fs::metadata(from).and_then(|meta| meta.modified()) {
+        Ok(mtime) => mtime,
+        Err(_) => return
+    };
+    let file = fs::OpenOptions::new().write(true).open(to)
+        .or_else(|_| fs::File::open(to));
+    if let Ok(file) = file {
+        let _ = file.set_modified(mtime);
+    }
+}
+
+fn copy_dir(src: &Path, dest: &Path) -> Result<(), String> {
+    fs::create_dir_all(dest)
+        .map_err(|e| format!("unable to create {}: {}", dest.display(), e))?;
+
+    let entries = fs::read_dir(src)
+        .map_err(|e| format!("unable to read {}: {}", src.display(), e))?;
+    for entry in entries {
+        let entry = entry
+            .map_err(|e| format!("unable to read {}: {}", src.display(), e))?;
+        if skip(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+
+        let from = entry.path();
+        let to = dest.join(entry.file_name());
+        if from.is_dir() {
+            copy_dir(&from, &to)?;
+        } else {
+            fs::copy(&from, &to).map_err(|e| {
+                format!("unable to copy {} to {}: {}",
+                        from.display(), to.display(), e)
+            })?;
+            keep_mtime(&from, &to);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use std::fs;
+use std::io;
+use std::path::{Path,PathBuf};
+
+pub struct Stamp {
+    path: PathBuf,
+    contents: String
+}
+
+impl Stamp {
+    pub fn new(path: &Path, fields: &[(&str, &str)]) -> Stamp {
+        let mut contents = String::new();
+        for (key, value) in fields.iter() {
+            contents.push_str(&format!("{}={}\n", key, value));
+        }
+
+        Stamp {
+            path: path.to_path_buf(),
+            contents
+        }
+    }
+
+    pub fn is_current(&self) -> bool {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => contents == self.contents,
+            Err(_) => false
+        }
+    }
+
+    // Whether the stamp is current and `file`, which it vouches for, is
+    // still next to it.
+    pub fn is_current_with(&self, file: &str) -> bool {
+        let exists = self.path.parent()
+            .is_some_and(|dir| dir.join(file).is_file());
+        exists && self.is_current()
+    }
+
+    pub fn remove(&self) {
+        let _ = fs::remove_file(&self.path);
+    }
+
+    pub fn write(&self) -> io::Result<()> {
+        fs::write(&self.path, &self.contents)
+    }
+}
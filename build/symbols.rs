@@ -0,0 +1,75 @@
+use build_config;
+use reproducible;
+
+use std::fs;
+use std::path::{Path,PathBuf};
+use std::process::{Command,Stdio};
+
+// Symbols libnewt exports, see newt.0.52.ver.
+const EXPORTS: [&str; 2] = ["newt*", "_newt*"];
+
+fn runs(program: &str) -> bool {
+    Command::new(program)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+// Whether the bundled slang and popt can be hidden inside libnewt.a. Needs
+// an ELF target and an objcopy for it.
+pub fn can_localize() -> bool {
+    let target = build_config::rust_target();
+    let elf = !build_config::is_darwin() &&
+              !target.contains("-windows") &&
+              !target.starts_with("wasm");
+    elf && runs(&build_config::objcopy())
+}
+
+fn run(cmd: &mut Command) -> Result<(), String> {
+    let output = cmd.output()
+        .map_err(|e| format!("unable to execute {:?}: {}", cmd, e))?;
+    if !output.status.success() {
+        return Err(format!("{:?} failed: {}", cmd,
+                           String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+// Link libnewt.a and the parts of `deps` it uses into a single object,
+// make everything but newt's own symbols local to it, and archive it as
+// `{out_dir}/link/libnewt.a`. Returns the directory of that archive.
+pub fn localize(newt: &Path, deps: &[PathBuf], out_dir: &Path)
+    -> Result<PathBuf, String> {
+    let link_dir = out_dir.join("link");
+    fs::create_dir_all(&link_dir).map_err(|e| {
+        format!("unable to create {}: {}", link_dir.display(), e)
+    })?;
+
+    let object = link_dir.join("newt.o");
+    let mut cmd = build_config::compiler().to_command();
+    cmd.args(["-r", "-nostdlib", "-o"])
+        .arg(&object)
+        .arg("-Wl,--whole-archive")
+        .arg(newt)
+        .arg("-Wl,--no-whole-archive")
+        .args(deps);
+    run(&mut cmd)?;
+
+    let mut cmd = Command::new(build_config::objcopy());
+    cmd.arg("--wildcard");
+    for symbol in EXPORTS.iter() {
+        cmd.arg(format!("--keep-global-symbol={}", symbol));
+    }
+    run(cmd.arg(&object))?;
+
+    let archive = link_dir.join("libnewt.a");
+    let _ = fs::remove_file(&archive);
+    run(build_config::archiver_command()
+            .arg("crs")
+            .arg(&archive)
+            .arg(&object))?;
+    reproducible::normalize_archive(&archive)?;
+    Ok(link_dir)
+}
@@ -0,0 +1,50 @@
+use build_config;
+
+use std::time::{Duration,Instant};
+
+// How long each phase of building one package took, reported with
+// NEWT_SYS_TIMINGS.
+pub struct Timings {
+    package: String,
+    phases: Vec<(&'static str, Duration)>
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.1}s", duration.as_secs_f64())
+}
+
+impl Timings {
+    pub fn new(package: &str, version: &str) -> Timings {
+        Timings {
+            package: format!("{} {}", package, version),
+            phases: Vec::new()
+        }
+    }
+
+    // Add the time since `start` to `phase`.
+    pub fn record(&mut self, phase: &'static str, start: Instant) {
+        let elapsed = start.elapsed();
+        match self.phases.iter_mut().find(|&&mut (name, _)| name == phase) {
+            Some(&mut (_, ref mut total)) => *total += elapsed,
+            None => self.phases.push((phase, elapsed))
+        }
+    }
+
+    pub fn report(&self) {
+        if !build_config::timings() {
+            return;
+        }
+
+        if self.phases.is_empty() {
+            println!("cargo:warning=timings: {} was up to date",
+                     self.package);
+            return;
+        }
+        let phases: Vec<String> = self.phases.iter()
+            .map(|&(name, duration)| format!("{} {}", name, seconds(duration)))
+            .collect();
+        let total = self.phases.iter().map(|&(_, duration)| duration).sum();
+        println!("cargo:warning=timings: {}: {} (total {})",
+                 self.package, phases.join(", "), seconds(total));
+    }
+}
@@ -0,0 +1,57 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Numeric components of a dotted version string, ignoring anything that
+// isn't a number (e.g. "0.52.21-rc1" gives [0, 52, 21]).
+pub fn parse(version: &str) -> Vec<u32> {
+    version.split('.')
+        .map(|part| {
+            let digits: String = part.chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            digits.parse().unwrap_or(0)
+        })
+        .collect()
+}
+
+pub fn compare(a: &str, b: &str) -> Ordering {
+    parse(a).cmp(&parse(b))
+}
+
+fn option_str(value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("Some({:?})", value),
+        None => String::from("None")
+    }
+}
+
+fn option_part(version: Option<&str>, idx: usize) -> String {
+    match version.and_then(|version| parse(version).get(idx).cloned()) {
+        Some(part) => format!("Some({})", part),
+        None => String::from("None")
+    }
+}
+
+// Write the `version` module included by lib.rs to `{out_dir}/version.rs`.
+pub fn write_module(out_dir: &Path, newt: Option<&str>, popt: Option<&str>,
+                    slang: Option<&str>) -> io::Result<()> {
+    let mut module = String::new();
+    module.push_str(&format!(
+        "/// Version of the linked libnewt, if known.\n\
+         pub const NEWT_VERSION: Option<&str> = {};\n\
+         pub const NEWT_VERSION_MAJOR: Option<u32> = {};\n\
+         pub const NEWT_VERSION_MINOR: Option<u32> = {};\n\
+         pub const NEWT_VERSION_PATCH: Option<u32> = {};\n",
+        option_str(newt), option_part(newt, 0), option_part(newt, 1),
+        option_part(newt, 2)));
+    module.push_str(&format!(
+        "/// Version of the popt libnewt was built against, if known.\n\
+         pub const POPT_VERSION: Option<&str> = {};\n\
+         /// Version of the slang libnewt was built against, if known.\n\
+         pub const SLANG_VERSION: Option<&str> = {};\n",
+        option_str(popt), option_str(slang)));
+
+    fs::write(out_dir.join("version.rs"), module)
+}
@@ -1,10 +1,21 @@
+#[cfg(not(newt_sys_no_c_shim))]
 use std::os::raw::c_int;
 
+#[cfg(not(newt_sys_no_c_shim))]
 extern "C" {
     fn newt_sys__colorset_custom(i: c_int) -> c_int;
 }
 
+#[cfg(not(newt_sys_no_c_shim))]
 #[inline]
 pub fn NEWT_COLORSET_CUSTOM(i: i32) -> i32 {
     unsafe { newt_sys__colorset_custom(i) }
 }
+
+// The definition from newt.h, used when there's no C compiler to build the
+// shim with.
+#[cfg(newt_sys_no_c_shim)]
+#[inline]
+pub fn NEWT_COLORSET_CUSTOM(i: i32) -> i32 {
+    30 + i
+}
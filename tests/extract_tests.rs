@@ -0,0 +1,71 @@
+/// The build script's archive extraction, built on its own so that its
+/// unit tests run too
+extern crate bzip2;
+extern crate flate2;
+extern crate tar;
+
+#[path = "../build/extract.rs"]
+mod extract;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::fs::{self,File};
+use std::path::{Path,PathBuf};
+use tar::{Builder,EntryType,Header};
+
+// An empty directory of its own for `name`.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A .tar.gz in `dir` with a file and a symbolic link to `link`.
+fn archive(dir: &Path, link: &str) -> PathBuf {
+    let path = dir.join("test.tar.gz");
+    let file = File::create(&path).unwrap();
+    let mut builder = Builder::new(GzEncoder::new(file, Compression::fast()));
+
+    let contents = b"newt";
+    let mut header = Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, "pkg/lib/libnewt.so.0.52",
+                        &contents[..]).unwrap();
+
+    let mut header = Header::new_gnu();
+    header.set_entry_type(EntryType::Symlink);
+    header.set_size(0);
+    header.set_mode(0o777);
+    builder.append_link(&mut header, "pkg/lib/libnewt.so", link).unwrap();
+
+    builder.into_inner().unwrap().finish().unwrap();
+    path
+}
+
+#[test]
+fn extracts_relative_symlinks() {
+    let dir = test_dir("extract-relative");
+    let archive = archive(&dir, "libnewt.so.0.52");
+    let dest = dir.join("dest");
+    fs::create_dir_all(&dest).unwrap();
+
+    extract::extract(&archive, &dest).unwrap();
+    let link = dest.join("pkg/lib/libnewt.so");
+    assert_eq!(fs::read_link(&link).unwrap(), Path::new("libnewt.so.0.52"));
+    assert_eq!(fs::read(&link).unwrap(), b"newt");
+}
+
+#[test]
+fn refuses_escaping_symlinks() {
+    let dir = test_dir("extract-escaping");
+    let archive = archive(&dir, "../../../outside");
+    let dest = dir.join("dest");
+    fs::create_dir_all(&dest).unwrap();
+
+    let err = extract::extract(&archive, &dest).unwrap_err();
+    assert!(err.contains("outside of the destination"), "{}", err);
+    assert!(!dest.join("pkg/lib/libnewt.so").exists());
+}
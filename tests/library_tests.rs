@@ -0,0 +1,140 @@
+/// How the build script links the libraries it finds, built on its own
+extern crate cc;
+extern crate lazy_static;
+extern crate pkg_config;
+extern crate shlex;
+#[cfg(feature = "vcpkg")]
+extern crate vcpkg;
+
+#[allow(dead_code)]
+#[path = "../build/build_config.rs"]
+mod build_config;
+#[allow(dead_code)]
+#[path = "../build/library.rs"]
+mod library;
+
+use library::Library;
+use std::env;
+use std::fs;
+use std::path::{Path,PathBuf};
+
+// An empty directory of its own for `name`.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("library_tests").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A sysroot standing in for the system's directories, which are all under
+// it. Every test sets the same one.
+fn sysroot() -> PathBuf {
+    let sysroot = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("library_tests-sysroot");
+    env::set_var("PKG_CONFIG_SYSROOT_DIR", &sysroot);
+    sysroot
+}
+
+// `dir` with an archive of each of `libs`. libm.a is an ld script, as
+// glibc installs it.
+fn lib_dir(dir: PathBuf, libs: &[&str]) -> PathBuf {
+    fs::create_dir_all(&dir).unwrap();
+    for lib in libs.iter() {
+        let contents = if *lib == "m" {
+            "GROUP ( /lib/libm.so.6 /usr/lib/libmvec.a )\n"
+        } else {
+            "!<arch>\n"
+        };
+        fs::write(dir.join(format!("lib{}.a", lib)), contents).unwrap();
+    }
+    dir
+}
+
+fn library(link_paths: Vec<PathBuf>, libs: &[&str]) -> Library {
+    Library {
+        include_paths: Vec::new(),
+        link_paths,
+        libs: libs.iter().map(|lib| lib.to_string()).collect(),
+        version: None,
+        root: None,
+        requires: Vec::new()
+    }
+}
+
+fn link_libs(library: &Library) -> Vec<String> {
+    library.cargo_metadata(true).into_iter()
+        .filter_map(|line| {
+            line.strip_prefix("cargo:rustc-link-lib=").map(String::from)
+        })
+        .collect()
+}
+
+#[test]
+fn system_libraries_linked_dynamically() {
+    let system = lib_dir(sysroot().join("usr/lib"),
+                         &["newt", "slang", "popt", "m", "dl"]);
+    let other = lib_dir(test_dir("system").join("lib"), &["m"]);
+    let library = library(vec![system, other],
+                          &["newt", "slang", "popt", "m", "dl"]);
+
+    assert_eq!(link_libs(&library),
+               ["static=newt", "static=slang", "popt", "m", "dl"]);
+}
+
+#[test]
+fn separate_libraries_linked_statically() {
+    let system = lib_dir(sysroot().join("usr/lib"), &["slang"]);
+    let prefix = lib_dir(test_dir("separate").join("lib"),
+                         &["newt", "popt", "m"]);
+    let library = library(vec![prefix, system],
+                          &["newt", "popt", "slang", "m"]);
+
+    assert_eq!(link_libs(&library),
+               ["static=newt", "static=popt", "slang", "m"]);
+}
+
+#[test]
+fn dynamic_linking_has_no_static_libraries() {
+    let prefix = lib_dir(test_dir("dynamic").join("lib"), &["newt", "slang"]);
+    let library = library(vec![prefix.clone()], &["newt", "slang"]);
+
+    assert_eq!(library.cargo_metadata(false), [
+        format!("cargo:rustc-link-search=native={}", prefix.display()),
+        String::from("cargo:rustc-link-lib=newt"),
+        String::from("cargo:rustc-link-lib=slang")
+    ]);
+}
+
+// Build for FreeBSD, where a libnewt.a without a .pc file is linked with
+// libintl too, as its packages build newt with NLS. Every test setting the
+// target sets this one.
+fn freebsd() {
+    env::set_var("TARGET", "x86_64-unknown-freebsd");
+    env::set_var("HOST", "x86_64-unknown-freebsd");
+}
+
+#[test]
+fn archive_links_only_newt_and_slang_statically() {
+    freebsd();
+    let dir = lib_dir(sysroot().join("usr/local/lib"),
+                      &["newt", "slang", "intl", "m"]);
+    let library = library::find_archive().unwrap();
+
+    assert_eq!(library.link_paths[0], dir);
+    assert_eq!(link_libs(&library),
+               ["static=newt", "static=slang", "intl", "m"]);
+}
+
+#[test]
+fn env_dirs_link_only_newt_and_slang_statically() {
+    freebsd();
+    sysroot();
+    let dir = lib_dir(test_dir("env_dirs").join("lib"),
+                      &["newt", "slang", "intl", "m"]);
+    env::set_var("NEWT_LIB_DIR", &dir);
+    let library = library::from_env_dirs(true).unwrap();
+
+    assert_eq!(link_libs(&library),
+               ["static=newt", "static=slang", "intl", "m"]);
+}
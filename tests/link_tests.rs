@@ -44,6 +44,32 @@ fn checkbox_tree_entry_value_types() {
     }
 }
 
+#[test]
+fn colorset_custom_follows_newt_h() {
+    assert_eq!(NEWT_COLORSET_CUSTOM(0), 30);
+    assert_eq!(NEWT_COLORSET_CUSTOM(5), 35);
+}
+
+#[test]
+fn linked_version_matches_version_module() {
+    let linked: Option<&'static str> = newt_linked_version();
+    assert_eq!(linked, version::NEWT_VERSION);
+    if let Some(linked) = linked {
+        assert!(linked.starts_with("0.52."));
+    }
+}
+
+#[test]
+fn build_info_describes_build() {
+    assert_eq!(build_info::NEWT_VERSION, version::NEWT_VERSION);
+    assert_eq!(build_info::FEATURES.contains(&"utf8"), cfg!(feature = "utf8"));
+    assert_eq!(build_info::FEATURES.contains(&"shared"),
+               cfg!(feature = "shared"));
+    if build_info::VENDORED {
+        assert!(build_info::SLANG_VERSION.is_some());
+    }
+}
+
 fn add_item(tree: newtComponent, text: &str, data: i32, indexes: &[i32]) {
     unsafe {
         let c_str = CString::new(text).unwrap();
@@ -0,0 +1,53 @@
+/// The build stamps, which decide whether a bundled library's source tree
+/// can skip configure
+#[allow(dead_code)]
+#[path = "../build/stamp.rs"]
+mod stamp;
+
+use stamp::Stamp;
+use std::fs;
+use std::path::{Path,PathBuf};
+
+// An empty directory of its own for `name`.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("stamp_tests").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn configured(dir: &Path, cflags: &str) -> Stamp {
+    Stamp::new(&dir.join(".newt-sys-stamp"), &[
+        ("package", "slang"),
+        ("version", "2.3.3"),
+        ("cflags", cflags)
+    ])
+}
+
+#[test]
+fn configured_tree_reused() {
+    let dir = test_dir("reused");
+    configured(&dir, "-O2").write().unwrap();
+    fs::write(dir.join("config.status"), "#!/bin/sh\n").unwrap();
+
+    assert!(configured(&dir, "-O2").is_current_with("config.status"));
+}
+
+#[test]
+fn configured_differently_not_reused() {
+    let dir = test_dir("different");
+    configured(&dir, "-O2").write().unwrap();
+    fs::write(dir.join("config.status"), "#!/bin/sh\n").unwrap();
+
+    assert!(!configured(&dir, "-O0").is_current_with("config.status"));
+}
+
+#[test]
+fn removed_tree_not_reused() {
+    let dir = test_dir("removed");
+    configured(&dir, "-O2").write().unwrap();
+
+    assert!(configured(&dir, "-O2").is_current());
+    assert!(!configured(&dir, "-O2").is_current_with("config.status"));
+}